@@ -0,0 +1,49 @@
+//! `#[derive(FromRow)]`, matching a `tiberius::Row`'s columns to a
+//! struct's fields by name.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+#[proc_macro_derive(FromRow)]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let ident = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(ident, "FromRow requires named fields")
+                    .to_compile_error()
+                    .into()
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(ident, "FromRow can only be derived for structs")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let assignments = fields.iter().map(|field| {
+        let name = field.ident.as_ref().expect("checked for named fields above");
+        let column = name.to_string();
+
+        quote! {
+            #name: row.try_get(#column)?
+        }
+    });
+
+    let expanded = quote! {
+        impl ::tiberius::FromRow for #ident {
+            fn from_row(row: ::tiberius::Row) -> ::tiberius::Result<Self> {
+                Ok(Self {
+                    #(#assignments),*
+                })
+            }
+        }
+    };
+
+    expanded.into()
+}
@@ -0,0 +1,32 @@
+//! Expands an `async fn(conn: tiberius::Client<S>) -> tiberius::Result<()>`
+//! into a real `#[test]` that dials `crate::test_transport()` and runs the
+//! body against it. Tests produced this way need a live server speaking
+//! tiberius's wire protocol, so
+//! they're marked `#[ignore]`; run them explicitly once one is reachable.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, ItemFn};
+
+#[proc_macro_attribute]
+pub fn test_on_runtimes(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as ItemFn);
+    let name = &input.sig.ident;
+    let vis = &input.vis;
+
+    let expanded = quote! {
+        #[test]
+        #[ignore = "requires a live server speaking tiberius's wire protocol; set TIBERIUS_TEST_CONNECTION_STRING"]
+        #vis fn #name() -> ::tiberius::Result<()> {
+            #input
+
+            ::futures_executor::block_on(async {
+                let transport = crate::test_transport()?;
+                let conn = ::tiberius::Config::new().connect(transport).await?;
+                #name(conn).await
+            })
+        }
+    };
+
+    expanded.into()
+}
@@ -0,0 +1,238 @@
+use crate::tds::codec::ColumnData;
+use crate::{IntoSql, Result};
+
+/// A single write to fold into one [`Client::batch_write`] round-trip.
+///
+/// [`Client::batch_write`]: client/struct.Client.html#method.batch_write
+#[derive(Debug, Clone)]
+pub enum WriteModel {
+    /// `INSERT INTO table (..row columns..) VALUES (..row values..)`.
+    Insert { table: String, row: Vec<Assignment> },
+    /// `UPDATE table SET ..set.. WHERE ..filter..`.
+    Update {
+        table: String,
+        set: Vec<Assignment>,
+        filter: Vec<Assignment>,
+    },
+    /// `DELETE FROM table WHERE ..filter..`.
+    Delete { table: String, filter: Vec<Assignment> },
+}
+
+/// A `column = value` pair used to build an `INSERT` column list, an
+/// `UPDATE`'s `SET` list, or a `WHERE` clause. `value` flows through the
+/// same [`IntoSql`] conversion as every other bound parameter, so it's
+/// sent to the server as a parameter rather than interpolated into the
+/// SQL text.
+#[derive(Debug, Clone)]
+pub struct Assignment {
+    pub(crate) column: String,
+    pub(crate) value: ColumnData,
+}
+
+impl Assignment {
+    pub fn new(column: impl Into<String>, value: impl IntoSql) -> Self {
+        Self {
+            column: column.into(),
+            value: value.into_sql(),
+        }
+    }
+}
+
+/// The outcome of a [`Client::batch_write`] call: the number of rows each
+/// write model affected, in the order they were given, plus the index of
+/// the first one the server reported as failed, if any.
+///
+/// [`Client::batch_write`]: client/struct.Client.html#method.batch_write
+#[derive(Debug, Clone, Default)]
+pub struct BatchWriteResult {
+    affected_rows: Vec<u64>,
+    first_failure: Option<usize>,
+}
+
+impl BatchWriteResult {
+    pub(crate) fn from_statement_results(results: Vec<Result<u64>>) -> Self {
+        let first_failure = results.iter().position(|result| result.is_err());
+        let affected_rows = results.into_iter().map(|result| result.unwrap_or(0)).collect();
+
+        Self {
+            affected_rows,
+            first_failure,
+        }
+    }
+
+    /// The number of rows each write model affected, indexed the same way
+    /// as the `Vec<WriteModel>` passed to `batch_write`.
+    pub fn affected_rows(&self) -> &[u64] {
+        &self.affected_rows
+    }
+
+    /// The index, into the original `Vec<WriteModel>`, of the first
+    /// statement the server reported as failed. `None` if every write
+    /// succeeded.
+    pub fn first_failure(&self) -> Option<usize> {
+        self.first_failure
+    }
+}
+
+/// Appends `model` as one statement of the batch to `sql`, pushing its
+/// bound values onto `params` in the same order as their placeholders and
+/// advancing `param_index` past them.
+pub(crate) fn append_statement(
+    sql: &mut String,
+    params: &mut Vec<ColumnData>,
+    param_index: &mut usize,
+    model: WriteModel,
+) {
+    match model {
+        WriteModel::Insert { table, row } => {
+            let columns = row
+                .iter()
+                .map(|assignment| assignment.column.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let placeholders = placeholders_for(row.len(), param_index);
+
+            sql.push_str(&format!(
+                "INSERT INTO {table} ({columns}) VALUES ({placeholders})"
+            ));
+
+            params.extend(row.into_iter().map(|assignment| assignment.value));
+        }
+        WriteModel::Update { table, set, filter } => {
+            let set_clause = render_clause(&set, param_index, " = ", ", ");
+            let where_clause = render_clause(&filter, param_index, " = ", " AND ");
+
+            sql.push_str(&format!("UPDATE {table} SET {set_clause} WHERE {where_clause}"));
+
+            params.extend(set.into_iter().map(|assignment| assignment.value));
+            params.extend(filter.into_iter().map(|assignment| assignment.value));
+        }
+        WriteModel::Delete { table, filter } => {
+            let where_clause = render_clause(&filter, param_index, " = ", " AND ");
+
+            sql.push_str(&format!("DELETE FROM {table} WHERE {where_clause}"));
+
+            params.extend(filter.into_iter().map(|assignment| assignment.value));
+        }
+    }
+}
+
+fn placeholders_for(count: usize, param_index: &mut usize) -> String {
+    (0..count)
+        .map(|_| {
+            let placeholder = format!("@p{param_index}");
+            *param_index += 1;
+            placeholder
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_clause(
+    assignments: &[Assignment],
+    param_index: &mut usize,
+    assign_with: &str,
+    join_with: &str,
+) -> String {
+    assignments
+        .iter()
+        .map(|assignment| {
+            let placeholder = format!("@p{param_index}");
+            *param_index += 1;
+            format!("{}{assign_with}{placeholder}", assignment.column)
+        })
+        .collect::<Vec<_>>()
+        .join(join_with)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_builds_a_column_list_and_placeholders() {
+        let mut sql = String::new();
+        let mut params = Vec::new();
+        let mut param_index = 0;
+
+        let model = WriteModel::Insert {
+            table: "users".into(),
+            row: vec![Assignment::new("id", 1i32), Assignment::new("name", "bob")],
+        };
+
+        append_statement(&mut sql, &mut params, &mut param_index, model);
+
+        assert_eq!("INSERT INTO users (id, name) VALUES (@p0, @p1)", sql);
+        assert_eq!(2, params.len());
+        assert_eq!(2, param_index);
+    }
+
+    #[test]
+    fn update_builds_a_set_and_where_clause_with_distinct_placeholders() {
+        let mut sql = String::new();
+        let mut params = Vec::new();
+        let mut param_index = 0;
+
+        let model = WriteModel::Update {
+            table: "users".into(),
+            set: vec![Assignment::new("name", "bob")],
+            filter: vec![Assignment::new("id", 1i32)],
+        };
+
+        append_statement(&mut sql, &mut params, &mut param_index, model);
+
+        assert_eq!("UPDATE users SET name = @p0 WHERE id = @p1", sql);
+        assert_eq!(2, params.len());
+    }
+
+    #[test]
+    fn delete_builds_a_where_clause() {
+        let mut sql = String::new();
+        let mut params = Vec::new();
+        let mut param_index = 0;
+
+        let model = WriteModel::Delete {
+            table: "users".into(),
+            filter: vec![Assignment::new("id", 1i32)],
+        };
+
+        append_statement(&mut sql, &mut params, &mut param_index, model);
+
+        assert_eq!("DELETE FROM users WHERE id = @p0", sql);
+        assert_eq!(1, params.len());
+    }
+
+    #[test]
+    fn param_index_keeps_advancing_across_statements() {
+        let mut sql = String::new();
+        let mut params = Vec::new();
+        let mut param_index = 0;
+
+        append_statement(
+            &mut sql,
+            &mut params,
+            &mut param_index,
+            WriteModel::Insert { table: "a".into(), row: vec![Assignment::new("x", 1i32)] },
+        );
+        sql.push_str("; ");
+        append_statement(
+            &mut sql,
+            &mut params,
+            &mut param_index,
+            WriteModel::Delete { table: "b".into(), filter: vec![Assignment::new("y", 2i32)] },
+        );
+
+        assert_eq!("INSERT INTO a (x) VALUES (@p0); DELETE FROM b WHERE y = @p1", sql);
+        assert_eq!(2, param_index);
+    }
+
+    #[test]
+    fn from_statement_results_reports_the_first_failure() {
+        let results = vec![Ok(1), Err(crate::Error::protocol("boom")), Ok(3)];
+        let result = BatchWriteResult::from_statement_results(results);
+
+        assert_eq!(&[1, 0, 3], result.affected_rows());
+        assert_eq!(Some(1), result.first_failure());
+    }
+}
@@ -0,0 +1,360 @@
+mod options;
+mod validate;
+
+pub use options::{BulkLoadOptions, OrderHint, SortDirection};
+
+use crate::tds::codec::{TokenColMetaData, TokenRow};
+use crate::tds::Connection;
+use crate::{Error, Result};
+use futures_util::io::{AsyncRead, AsyncWrite};
+use futures_util::sink::Sink;
+use futures_util::{future, Stream};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The default number of rows buffered before [`BulkLoadRequest::send`]
+/// transparently flushes them as a TDS bulk packet.
+///
+/// [`BulkLoadRequest::send`]: struct.BulkLoadRequest.html#method.send
+const DEFAULT_MAX_ROWS: usize = 1000;
+
+/// The outcome of a finished bulk load or statement, returned by
+/// [`BulkLoadRequest::finalize`] and [`Client::execute`].
+///
+/// [`BulkLoadRequest::finalize`]: struct.BulkLoadRequest.html#method.finalize
+/// [`Client::execute`]: ../client/struct.Client.html#method.execute
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExecuteResult {
+    total: u64,
+}
+
+impl ExecuteResult {
+    pub(crate) fn new(total: u64) -> Self {
+        Self { total }
+    }
+
+    /// The number of rows the server reports as inserted (bulk load) or
+    /// affected (statement).
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// An open `INSERT BULK` statement, accepting rows via [`send`] and
+/// finishing the transfer with [`finalize`].
+///
+/// Rows are buffered in memory until either [`with_max_rows`] or
+/// [`with_max_bytes`] is crossed, at which point `send` transparently
+/// flushes the buffer as a TDS bulk packet and keeps the statement open for
+/// more rows. By default only the row-count threshold applies, set to
+/// [`DEFAULT_MAX_ROWS`].
+///
+/// [`send`]: #method.send
+/// [`finalize`]: #method.finalize
+/// [`with_max_rows`]: #method.with_max_rows
+/// [`with_max_bytes`]: #method.with_max_bytes
+pub struct BulkLoadRequest<'a, S> {
+    connection: &'a mut Connection<S>,
+    columns: Vec<TokenColMetaData>,
+    strict_types: bool,
+    rows_sent: u64,
+    buffer: Vec<TokenRow>,
+    buffered_bytes: usize,
+    max_rows: Option<usize>,
+    max_bytes: Option<usize>,
+}
+
+impl<'a, S> BulkLoadRequest<'a, S>
+where
+    S: AsyncWrite + Unpin + Send,
+{
+    pub(crate) fn new(connection: &'a mut Connection<S>, columns: Vec<TokenColMetaData>) -> Self {
+        Self {
+            connection,
+            columns,
+            strict_types: true,
+            rows_sent: 0,
+            buffer: Vec::new(),
+            buffered_bytes: 0,
+            max_rows: Some(DEFAULT_MAX_ROWS),
+            max_bytes: None,
+        }
+    }
+
+    /// The target columns' server-reported metadata, as returned by the
+    /// `INSERT BULK` handshake. Empty if the server didn't report any
+    /// (e.g. the columns couldn't be resolved).
+    pub fn columns(&self) -> &[TokenColMetaData] {
+        &self.columns
+    }
+
+    /// Toggles client-side validation of pushed values against
+    /// [`columns`]' metadata (nullability, `VARCHAR(n)` length, gross
+    /// type mismatches). Enabled by default; pass `false` to fall back to
+    /// the old, permissive behavior where a mismatch only surfaces once
+    /// the server rejects the batch.
+    ///
+    /// [`columns`]: #method.columns
+    pub fn strict_types(mut self, strict: bool) -> Self {
+        self.strict_types = strict;
+        self
+    }
+
+    /// Flushes the buffer once more than `n` rows are pending. Pass `0` to
+    /// disable the row-count threshold and rely on [`with_max_bytes`]
+    /// alone.
+    ///
+    /// [`with_max_bytes`]: #method.with_max_bytes
+    pub fn with_max_rows(mut self, n: usize) -> Self {
+        self.max_rows = if n == 0 { None } else { Some(n) };
+        self
+    }
+
+    /// Flushes the buffer once the estimated serialized size of the
+    /// pending rows exceeds `n` bytes. Pass `0` to disable the byte
+    /// threshold and rely on [`with_max_rows`] alone.
+    ///
+    /// [`with_max_rows`]: #method.with_max_rows
+    pub fn with_max_bytes(mut self, n: usize) -> Self {
+        self.max_bytes = if n == 0 { None } else { Some(n) };
+        self
+    }
+
+    /// The number of rows currently buffered, not yet sent to the server.
+    pub fn buffered_rows(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// The estimated size, in bytes, of the currently buffered rows.
+    pub fn buffered_bytes(&self) -> usize {
+        self.buffered_bytes
+    }
+
+    /// Pushes a row into the send buffer, flushing it to the server first
+    /// if appending `row` would cross the configured row-count or
+    /// byte-size threshold. A flush never splits a row: the threshold is
+    /// checked, and the buffer flushed, before `row` is added.
+    ///
+    /// With [`strict_types`] enabled (the default), `row` is first checked
+    /// against [`columns`]' metadata, returning a client-side
+    /// [`Error::BulkInput`] naming the offending column and row index
+    /// instead of letting the server abort the whole batch.
+    ///
+    /// [`strict_types`]: #method.strict_types
+    /// [`columns`]: #method.columns
+    /// [`Error::BulkInput`]: ../error/enum.Error.html#variant.BulkInput
+    pub async fn send(&mut self, row: TokenRow) -> Result<()> {
+        if self.strict_types {
+            validate::validate_row(&self.columns, &row, self.rows_sent)?;
+        }
+        self.rows_sent += 1;
+
+        let row_bytes = row.wire_size_estimate();
+
+        let would_exceed_rows = self
+            .max_rows
+            .is_some_and(|max| self.buffer.len() + 1 > max);
+
+        let would_exceed_bytes = self
+            .max_bytes
+            .is_some_and(|max| self.buffered_bytes + row_bytes > max);
+
+        if (would_exceed_rows || would_exceed_bytes) && !self.buffer.is_empty() {
+            self.flush().await?;
+        }
+
+        self.buffered_bytes += row_bytes;
+        self.buffer.push(row);
+
+        Ok(())
+    }
+
+    /// A convenience for piping an infallible `Stream<Item = TokenRow>`
+    /// into this request with the same backpressure as the [`Sink`]
+    /// implementation. For a `Stream<Item = Result<TokenRow>>`, use
+    /// [`futures_util::SinkExt::send_all`] directly.
+    ///
+    /// [`Sink`]: #impl-Sink%3CTokenRow%3E-for-BulkLoadRequest%3C%27a%2C+S%3E
+    pub async fn send_all<St>(&mut self, stream: St) -> Result<()>
+    where
+        St: Stream<Item = TokenRow> + Unpin,
+    {
+        use futures_util::{SinkExt, StreamExt};
+
+        let mut stream = stream.map(Ok);
+        SinkExt::send_all(self, &mut stream).await
+    }
+
+    /// `true` once the buffer has crossed the configured row-count or
+    /// byte-size threshold and should be flushed before accepting more
+    /// rows.
+    fn over_threshold(&self) -> bool {
+        self.max_rows.is_some_and(|max| self.buffer.len() >= max)
+            || self.max_bytes.is_some_and(|max| self.buffered_bytes >= max)
+    }
+
+    /// Flushes any buffered rows as a TDS bulk packet, keeping the
+    /// `INSERT BULK` statement open, and resets the buffer counters.
+    fn poll_flush_buffer(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if self.buffer.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+
+        match self.connection.poll_flush_bulk_rows(cx, &self.buffer) {
+            Poll::Ready(Ok(())) => {
+                self.buffer.clear();
+                self.buffered_bytes = 0;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        future::poll_fn(|cx| self.poll_flush_buffer(cx)).await
+    }
+
+    /// Flushes the remaining buffered rows and closes the `INSERT BULK`
+    /// statement, returning the server-reported row count.
+    pub async fn finalize(mut self) -> Result<ExecuteResult>
+    where
+        S: AsyncRead,
+    {
+        self.flush().await?;
+        let total = self.connection.finish_bulk_insert().await?;
+
+        Ok(ExecuteResult::new(total))
+    }
+}
+
+impl<'a, S> Sink<TokenRow> for BulkLoadRequest<'a, S>
+where
+    S: AsyncWrite + Unpin + Send,
+{
+    type Error = Error;
+
+    /// Gates on the underlying socket's writability: once the buffer has
+    /// crossed its threshold, this flushes before accepting the next row,
+    /// which is how backpressure propagates to the upstream `Stream`.
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+
+        if this.over_threshold() {
+            this.poll_flush_buffer(cx)
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: TokenRow) -> Result<()> {
+        let this = self.get_mut();
+
+        if this.strict_types {
+            validate::validate_row(&this.columns, &item, this.rows_sent)?;
+        }
+        this.rows_sent += 1;
+
+        this.buffered_bytes += item.wire_size_estimate();
+        this.buffer.push(item);
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().poll_flush_buffer(cx)
+    }
+
+    /// Flushes the remainder; the `INSERT BULK` statement itself is closed
+    /// only by [`finalize`]-ing the request, not by closing the sink or by
+    /// dropping the request.
+    ///
+    /// [`finalize`]: #method.finalize
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tds::codec::{ColumnData, TypeInfo};
+    use crate::tds::packet;
+    use crate::tds::test_util::MockTransport;
+    use futures_util::stream;
+
+    fn columns() -> Vec<TokenColMetaData> {
+        vec![TokenColMetaData { name: "id".into(), type_info: TypeInfo::Int, nullable: true }]
+    }
+
+    fn row(value: i32) -> TokenRow {
+        let mut row = TokenRow::new();
+        row.push(ColumnData::I32(Some(value)));
+        row
+    }
+
+    #[test]
+    fn poll_ready_flushes_once_the_buffer_is_over_threshold() {
+        let mut conn = Connection::new(MockTransport::new());
+
+        futures_executor::block_on(async {
+            let mut req = BulkLoadRequest::new(&mut conn, columns()).with_max_rows(1);
+
+            Pin::new(&mut req).start_send(row(1)).unwrap();
+            assert_eq!(1, req.buffered_rows());
+
+            future::poll_fn(|cx| Pin::new(&mut req).poll_ready(cx)).await.unwrap();
+            assert_eq!(0, req.buffered_rows());
+        });
+
+        assert_eq!(packet::BULK_ROWS, conn.transport().written[0]);
+    }
+
+    #[test]
+    fn start_send_rejects_a_row_that_fails_strict_type_validation() {
+        futures_executor::block_on(async {
+            let mut conn = Connection::new(MockTransport::new());
+            let mut req = BulkLoadRequest::new(&mut conn, columns());
+
+            let mut bad_row = TokenRow::new();
+            bad_row.push(ColumnData::String(Some("not an int".into())));
+
+            assert!(Pin::new(&mut req).start_send(bad_row).is_err());
+            assert_eq!(0, req.buffered_rows());
+        });
+    }
+
+    #[test]
+    fn poll_flush_and_poll_close_both_drain_the_buffer() {
+        futures_executor::block_on(async {
+            let mut conn = Connection::new(MockTransport::new());
+            let mut req = BulkLoadRequest::new(&mut conn, columns());
+
+            Pin::new(&mut req).start_send(row(1)).unwrap();
+            future::poll_fn(|cx| Pin::new(&mut req).poll_flush(cx)).await.unwrap();
+            assert_eq!(0, req.buffered_rows());
+
+            Pin::new(&mut req).start_send(row(2)).unwrap();
+            future::poll_fn(|cx| Pin::new(&mut req).poll_close(cx)).await.unwrap();
+
+            assert_eq!(0, req.buffered_rows());
+            assert_eq!(0, req.buffered_bytes());
+        });
+    }
+
+    #[test]
+    fn send_all_drains_the_stream_and_flushes_on_completion() {
+        futures_executor::block_on(async {
+            let mut transport = MockTransport::new();
+            transport.push_packet(packet::BULK_DONE_OK, &2u64.to_le_bytes());
+            let mut conn = Connection::new(transport);
+
+            let mut req = BulkLoadRequest::new(&mut conn, columns());
+            req.send_all(stream::iter(vec![row(1), row(2)])).await.unwrap();
+            assert_eq!(0, req.buffered_rows());
+
+            let result = req.finalize().await.unwrap();
+            assert_eq!(2, result.total());
+        });
+    }
+}
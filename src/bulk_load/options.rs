@@ -0,0 +1,30 @@
+/// Sort direction for a [`BulkLoadOptions`] order hint.
+///
+/// [`BulkLoadOptions`]: struct.BulkLoadOptions.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Tells the server the incoming rows are already sorted by the given
+/// column, letting it skip a sort step when the target has a clustered
+/// index on it.
+#[derive(Debug, Clone)]
+pub struct OrderHint {
+    pub column: String,
+    pub direction: SortDirection,
+}
+
+/// Tuning knobs for [`Client::bulk_insert_with_options`].
+///
+/// [`Client::bulk_insert_with_options`]: ../client/struct.Client.html#method.bulk_insert_with_options
+#[derive(Debug, Clone, Default)]
+pub struct BulkLoadOptions {
+    /// Validate `CHECK` constraints on the target table while loading.
+    pub check_constraints: bool,
+    /// Run `INSTEAD OF`/`AFTER` triggers defined on the target table.
+    pub fire_triggers: bool,
+    /// Treat empty string/zero-length input as `NULL` instead of as-is.
+    pub keep_nulls: bool,
+}
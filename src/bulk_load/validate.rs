@@ -0,0 +1,113 @@
+use crate::tds::codec::{ColumnData, TokenColMetaData, TokenRow, TypeInfo};
+use crate::{Error, Result};
+
+/// Validates `row` against the target table's `COLMETADATA`, matching
+/// columns positionally. A no-op if `columns` is empty (metadata wasn't
+/// available).
+pub(crate) fn validate_row(
+    columns: &[TokenColMetaData],
+    row: &TokenRow,
+    row_index: u64,
+) -> Result<()> {
+    for (position, (data, column)) in row.columns().iter().zip(columns).enumerate() {
+        if data.is_null() && !column.nullable {
+            return Err(Error::bulk_input(format!(
+                "row {row_index}, column {position} (`{}`): NULL is not allowed",
+                column.name
+            )));
+        }
+
+        if let (ColumnData::String(Some(s)), TypeInfo::VarChar {
+            max_length: Some(max),
+        }) = (data, &column.type_info)
+        {
+            let len = s.chars().count();
+
+            if len > *max {
+                return Err(Error::bulk_input(format!(
+                    "row {row_index}, column {position} (`{}`): value of {len} characters \
+                     exceeds VARCHAR({max})",
+                    column.name
+                )));
+            }
+        }
+
+        if !data.is_null() && !type_matches(data, &column.type_info) {
+            return Err(Error::bulk_input(format!(
+                "row {row_index}, column {position} (`{}`): value is not a {:?}",
+                column.name, column.type_info
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn type_matches(data: &ColumnData, type_info: &TypeInfo) -> bool {
+    match (data, type_info) {
+        (ColumnData::U8(_), TypeInfo::TinyInt) => true,
+        (ColumnData::I16(_), TypeInfo::SmallInt) => true,
+        (ColumnData::I32(_), TypeInfo::Int) => true,
+        (ColumnData::I64(_), TypeInfo::BigInt) => true,
+        (ColumnData::F32(_), TypeInfo::Real) => true,
+        (ColumnData::F64(_), TypeInfo::Float) => true,
+        (ColumnData::Bit(_), TypeInfo::Bit) => true,
+        (ColumnData::String(_), TypeInfo::VarChar { .. }) => true,
+        #[cfg(feature = "chrono")]
+        (ColumnData::DateTime2(_), TypeInfo::DateTime2 { .. }) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, type_info: TypeInfo, nullable: bool) -> TokenColMetaData {
+        TokenColMetaData { name: name.to_owned(), type_info, nullable }
+    }
+
+    #[test]
+    fn rejects_null_in_a_non_nullable_column() {
+        let columns = vec![column("id", TypeInfo::Int, false)];
+        let mut row = TokenRow::new();
+        row.push(ColumnData::I32(None));
+
+        let err = validate_row(&columns, &row, 0).unwrap_err();
+        assert!(matches!(err, Error::BulkInput(_)));
+        assert!(err.to_string().contains("NULL is not allowed"));
+    }
+
+    #[test]
+    fn rejects_a_value_longer_than_varchar_n() {
+        let columns = vec![column("name", TypeInfo::VarChar { max_length: Some(3) }, false)];
+        let mut row = TokenRow::new();
+        row.push(ColumnData::String(Some("abcd".into())));
+
+        let err = validate_row(&columns, &row, 0).unwrap_err();
+        assert!(err.to_string().contains("exceeds VARCHAR(3)"));
+    }
+
+    #[test]
+    fn accepts_a_matching_row() {
+        let columns = vec![
+            column("id", TypeInfo::Int, false),
+            column("name", TypeInfo::VarChar { max_length: Some(10) }, true),
+        ];
+        let mut row = TokenRow::new();
+        row.push(ColumnData::I32(Some(1)));
+        row.push(ColumnData::String(None));
+
+        assert!(validate_row(&columns, &row, 0).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_gross_type_mismatch() {
+        let columns = vec![column("id", TypeInfo::Int, false)];
+        let mut row = TokenRow::new();
+        row.push(ColumnData::String(Some("not an int".into())));
+
+        let err = validate_row(&columns, &row, 0).unwrap_err();
+        assert!(err.to_string().contains("is not a"));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::tds::Connection;
+use crate::{Client, Result};
+use futures_util::io::{AsyncRead, AsyncWrite};
+
+/// Connection parameters for a [`Client`].
+///
+/// [`Client`]: struct.Client.html
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    host: Option<String>,
+    port: u16,
+    database: Option<String>,
+    statement_cache_capacity: usize,
+}
+
+impl Config {
+    /// Creates an empty configuration with SQL Server's default port and
+    /// the prepared-statement cache disabled.
+    pub fn new() -> Self {
+        Self {
+            host: None,
+            port: 1433,
+            database: None,
+            statement_cache_capacity: 0,
+        }
+    }
+
+    /// Sets the host to connect to.
+    pub fn host(&mut self, host: impl Into<String>) -> &mut Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    /// Sets the port to connect to.
+    pub fn port(&mut self, port: u16) -> &mut Self {
+        self.port = port;
+        self
+    }
+
+    /// Sets the initial database for the connection.
+    pub fn database(&mut self, database: impl Into<String>) -> &mut Self {
+        self.database = Some(database.into());
+        self
+    }
+
+    /// Sets how many prepared statements `Client` keeps around, keyed by
+    /// SQL text, before evicting the least recently used one. `0` (the
+    /// default) disables the cache: every `query`/`execute` call sends its
+    /// SQL text ad hoc, without `sp_prepare`.
+    pub fn statement_cache_capacity(&mut self, capacity: usize) -> &mut Self {
+        self.statement_cache_capacity = capacity;
+        self
+    }
+
+    /// Wraps an already-established transport in a ready-to-use
+    /// [`Client`]. No handshake is exchanged, so the host, port and
+    /// database set here are not sent to the server.
+    ///
+    /// [`Client`]: struct.Client.html
+    pub async fn connect<S>(self, transport: S) -> Result<Client<S>>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send,
+    {
+        Ok(Client::new(
+            Connection::new(transport),
+            self.statement_cache_capacity,
+        ))
+    }
+}
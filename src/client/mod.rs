@@ -0,0 +1,204 @@
+mod config;
+mod statement_cache;
+
+pub use config::Config;
+
+use crate::batch_write::{self, BatchWriteResult, WriteModel};
+use crate::bulk_load::{BulkLoadOptions, BulkLoadRequest, ExecuteResult, OrderHint, SortDirection};
+use crate::query::QueryStream;
+use crate::tds::codec::ColumnData;
+use crate::tds::Connection;
+use crate::{Result, ToSql};
+use futures_util::io::{AsyncRead, AsyncWrite};
+use statement_cache::{CachedStatement, StatementCache};
+
+/// A connection to a server speaking this crate's own wire protocol, and
+/// the entry point for running queries, statements and bulk loads.
+pub struct Client<S> {
+    connection: Connection<S>,
+    statement_cache: StatementCache,
+}
+
+impl<S> Client<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    pub(crate) fn new(connection: Connection<S>, statement_cache_capacity: usize) -> Self {
+        Self {
+            connection,
+            statement_cache: StatementCache::new(statement_cache_capacity),
+        }
+    }
+
+    /// Executes a statement and returns the number of affected rows.
+    pub async fn execute(
+        &mut self,
+        query: impl AsRef<str>,
+        params: &[&dyn ToSql],
+    ) -> Result<ExecuteResult> {
+        let values: Vec<ColumnData> = params.iter().map(|param| param.to_sql()).collect();
+
+        let total = match self.prepared_handle(query.as_ref()).await? {
+            Some(handle) => self.connection.execute_prepared(handle, &values).await?,
+            None => {
+                let results = self.connection.execute_batch(query.as_ref(), &values, 1).await?;
+
+                results
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| crate::Error::protocol("execute_batch returned no results"))??
+            }
+        };
+
+        Ok(ExecuteResult::new(total))
+    }
+
+    /// Runs a query and returns a `Stream` over its result rows, read
+    /// directly with [`Row::get`] or, via [`QueryStream::into_typed`], as
+    /// a `#[derive(FromRow)]` struct.
+    ///
+    /// [`Row::get`]: row/struct.Row.html#method.get
+    /// [`QueryStream::into_typed`]: query/struct.QueryStream.html#method.into_typed
+    pub async fn query(
+        &mut self,
+        query: impl AsRef<str>,
+        params: &[&dyn ToSql],
+    ) -> Result<QueryStream<'_, S>> {
+        let values: Vec<ColumnData> = params.iter().map(|param| param.to_sql()).collect();
+
+        let columns = match self.prepared_handle(query.as_ref()).await? {
+            Some(handle) => self.connection.query_prepared(handle, &values).await?,
+            None => self.connection.start_query(query.as_ref(), &values).await?,
+        };
+
+        Ok(QueryStream::new(&mut self.connection, columns))
+    }
+
+    /// The maximum number of prepared statements this client keeps around,
+    /// as set by [`Config::statement_cache_capacity`].
+    ///
+    /// [`Config::statement_cache_capacity`]: struct.Config.html#method.statement_cache_capacity
+    pub fn statement_cache_capacity(&self) -> usize {
+        self.statement_cache.capacity()
+    }
+
+    /// Drops every cached prepared statement, sending `sp_unprepare` for
+    /// each one. Dropping a `Client` without calling this leaks the
+    /// server-side handles until the connection itself closes.
+    pub async fn close(mut self) -> Result<()> {
+        for statement in self.statement_cache.drain() {
+            self.connection.unprepare_statement(statement.handle).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Resolves `sql` to a prepared-statement handle, reusing a cache hit
+    /// or `sp_prepare`-ing it on a miss, so the caller can run it with
+    /// [`crate::tds::connection::Connection::execute_prepared`] or
+    /// [`crate::tds::connection::Connection::query_prepared`]. When the
+    /// cache is at capacity, the least recently used handle is released
+    /// with `sp_unprepare`.
+    ///
+    /// Returns `None` when [`statement_cache_capacity`] is `0`: preparing
+    /// a handle just to unprepare it again on the next call would cost two
+    /// extra round trips for nothing, so the cache is skipped entirely and
+    /// the caller should run `sql` ad hoc instead.
+    ///
+    /// [`statement_cache_capacity`]: #method.statement_cache_capacity
+    async fn prepared_handle(&mut self, sql: &str) -> Result<Option<i32>> {
+        if self.statement_cache.capacity() == 0 {
+            return Ok(None);
+        }
+
+        if let Some(cached) = self.statement_cache.get(sql) {
+            return Ok(Some(cached.handle));
+        }
+
+        let (handle, _columns) = self.connection.prepare_statement(sql).await?;
+        let evicted = self
+            .statement_cache
+            .insert(sql.to_owned(), CachedStatement { handle });
+
+        if let Some(evicted) = evicted {
+            self.connection.unprepare_statement(evicted.handle).await?;
+        }
+
+        Ok(Some(handle))
+    }
+
+    /// Runs a heterogeneous list of inserts, updates and deletes as one
+    /// TDS batch, cutting the round-trips a workload that interleaves
+    /// writes across tables would otherwise need. Unlike [`bulk_insert`],
+    /// this isn't restricted to a single table or to `INSERT`.
+    ///
+    /// Every model's values still flow through the usual [`IntoSql`]
+    /// conversion and are sent as bound parameters, not interpolated into
+    /// the statement text.
+    ///
+    /// [`bulk_insert`]: #method.bulk_insert
+    /// [`IntoSql`]: ../trait.IntoSql.html
+    pub async fn batch_write(&mut self, models: Vec<WriteModel>) -> Result<BatchWriteResult> {
+        let statement_count = models.len();
+        let mut statement = String::new();
+        let mut params = Vec::new();
+        let mut param_index = 0;
+
+        for (i, model) in models.into_iter().enumerate() {
+            if i > 0 {
+                statement.push_str("; ");
+            }
+
+            batch_write::append_statement(&mut statement, &mut params, &mut param_index, model);
+        }
+
+        let results = self
+            .connection
+            .execute_batch(&statement, &params, statement_count)
+            .await?;
+
+        Ok(BatchWriteResult::from_statement_results(results))
+    }
+
+    /// Opens an `INSERT BULK` statement against `table`, inserting into
+    /// every column in server-reported order.
+    ///
+    /// See [`bulk_insert_with_options`] to target a subset of columns or
+    /// tune the load.
+    ///
+    /// [`bulk_insert_with_options`]: #method.bulk_insert_with_options
+    pub async fn bulk_insert(&mut self, table: &str) -> Result<BulkLoadRequest<'_, S>> {
+        self.bulk_insert_with_options(table, &[], BulkLoadOptions::default(), &[])
+            .await
+    }
+
+    /// Opens an `INSERT BULK` statement against `table`, restricted to
+    /// `columns` (server column order if empty) and tuned by `options` and
+    /// `order_hints`.
+    pub async fn bulk_insert_with_options(
+        &mut self,
+        table: &str,
+        columns: &[&str],
+        options: BulkLoadOptions,
+        order_hints: &[OrderHint],
+    ) -> Result<BulkLoadRequest<'_, S>> {
+        let hints: Vec<(&str, bool)> = order_hints
+            .iter()
+            .map(|hint| (hint.column.as_str(), hint.direction == SortDirection::Desc))
+            .collect();
+
+        let metadata = self
+            .connection
+            .prepare_bulk_insert(
+                table,
+                columns,
+                options.check_constraints,
+                options.fire_triggers,
+                options.keep_nulls,
+                &hints,
+            )
+            .await?;
+
+        Ok(BulkLoadRequest::new(&mut self.connection, metadata))
+    }
+}
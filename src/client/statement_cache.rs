@@ -0,0 +1,142 @@
+use std::collections::{HashMap, VecDeque};
+
+/// A prepared statement's server-assigned handle.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CachedStatement {
+    pub(crate) handle: i32,
+}
+
+/// Maps SQL text to its prepared-statement handle so repeated queries can
+/// skip straight to `sp_execute`, evicting the least recently used entry
+/// once `capacity` is exceeded.
+///
+/// A `capacity` of `0` disables the cache: callers are expected to check
+/// [`capacity`] and skip `sp_prepare` entirely rather than calling [`get`]
+/// or [`insert`] at all. [`insert`] still reports the no-op, straight-back
+/// eviction its capacity would imply, for anything that calls it anyway.
+///
+/// [`capacity`]: #method.capacity
+/// [`get`]: #method.get
+/// [`insert`]: #method.insert
+pub(crate) struct StatementCache {
+    capacity: usize,
+    entries: HashMap<String, CachedStatement>,
+    // Front = least recently used, back = most recently used.
+    recency: VecDeque<String>,
+}
+
+impl StatementCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: VecDeque::new(),
+        }
+    }
+
+    /// The maximum number of prepared statements this cache keeps around
+    /// before evicting the least recently used one.
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Looks up `sql`, marking it most recently used on a hit.
+    pub(crate) fn get(&mut self, sql: &str) -> Option<&CachedStatement> {
+        if self.entries.contains_key(sql) {
+            self.touch(sql);
+        }
+
+        self.entries.get(sql)
+    }
+
+    /// Inserts a freshly prepared statement, evicting and returning the
+    /// least recently used entry if the cache is now over capacity. The
+    /// caller is responsible for sending `sp_unprepare` for any returned
+    /// entry.
+    pub(crate) fn insert(
+        &mut self,
+        sql: String,
+        statement: CachedStatement,
+    ) -> Option<CachedStatement> {
+        if self.capacity == 0 {
+            return Some(statement);
+        }
+
+        self.entries.insert(sql.clone(), statement);
+        self.recency.push_back(sql);
+
+        if self.entries.len() > self.capacity {
+            let lru = self.recency.pop_front()?;
+            self.entries.remove(&lru)
+        } else {
+            None
+        }
+    }
+
+    /// Removes every cached statement, handing them back so the caller can
+    /// send `sp_unprepare` for each one (e.g. on [`Client::close`]).
+    ///
+    /// [`Client::close`]: struct.Client.html#method.close
+    pub(crate) fn drain(&mut self) -> Vec<CachedStatement> {
+        self.recency.clear();
+        self.entries.drain().map(|(_, v)| v).collect()
+    }
+
+    fn touch(&mut self, sql: &str) {
+        if let Some(pos) = self.recency.iter().position(|s| s == sql) {
+            let sql = self.recency.remove(pos).unwrap();
+            self.recency.push_back(sql);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statement(handle: i32) -> CachedStatement {
+        CachedStatement { handle }
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_entry_once_over_capacity() {
+        let mut cache = StatementCache::new(2);
+        assert_eq!(2, cache.capacity());
+
+        assert!(cache.insert("a".into(), statement(1)).is_none());
+        assert!(cache.insert("b".into(), statement(2)).is_none());
+
+        // Touch "a" so "b" becomes the least recently used.
+        assert_eq!(1, cache.get("a").unwrap().handle);
+
+        let evicted = cache.insert("c".into(), statement(3)).unwrap();
+        assert_eq!(2, evicted.handle);
+
+        assert!(cache.get("b").is_none());
+        assert_eq!(1, cache.get("a").unwrap().handle);
+        assert_eq!(3, cache.get("c").unwrap().handle);
+    }
+
+    #[test]
+    fn zero_capacity_hands_every_insert_straight_back_for_eviction() {
+        let mut cache = StatementCache::new(0);
+        assert_eq!(0, cache.capacity());
+
+        let evicted = cache.insert("a".into(), statement(1)).unwrap();
+        assert_eq!(1, evicted.handle);
+        assert!(cache.get("a").is_none());
+    }
+
+    #[test]
+    fn drain_returns_every_entry_and_empties_the_cache() {
+        let mut cache = StatementCache::new(2);
+        cache.insert("a".into(), statement(1));
+        cache.insert("b".into(), statement(2));
+
+        let mut handles: Vec<i32> = cache.drain().iter().map(|s| s.handle).collect();
+        handles.sort();
+
+        assert_eq!(vec![1, 2], handles);
+        assert!(cache.get("a").is_none());
+    }
+}
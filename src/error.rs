@@ -0,0 +1,48 @@
+use std::io;
+
+/// A result type that defaults to [`Error`] for the error case.
+///
+/// [`Error`]: enum.Error.html
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The `Error` type, representing all errors that can occur in this crate.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// An error occurred while reading or writing to the underlying I/O
+    /// resource.
+    #[error("IO Error: {0}")]
+    Io(#[from] io::Error),
+
+    /// A protocol error, most likely something unexpected from the server.
+    #[error("Protocol error: {0}")]
+    Protocol(std::borrow::Cow<'static, str>),
+
+    /// The server returned an error as part of its response.
+    #[error("Server error, code: {code}, message: {message}")]
+    Server {
+        /// The server-reported error code.
+        code: u32,
+        /// The human-readable message describing the error.
+        message: String,
+    },
+
+    /// Caller passed data that cannot be sent to the server as-is, e.g. a
+    /// value that doesn't fit the column it's being bound to.
+    #[error("{0}")]
+    BulkInput(std::borrow::Cow<'static, str>),
+
+    /// Something in the driver reached a state it should never be able to
+    /// reach.
+    #[error("{0}")]
+    Internal(std::borrow::Cow<'static, str>),
+}
+
+impl Error {
+    pub(crate) fn protocol(s: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        Self::Protocol(s.into())
+    }
+
+    pub(crate) fn bulk_input(s: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        Self::BulkInput(s.into())
+    }
+}
@@ -0,0 +1,92 @@
+use crate::tds::codec::ColumnData;
+use crate::{Error, Result};
+
+/// Converts a [`ColumnData`] value read back off the wire into a Rust
+/// value. The inverse of [`IntoSql`].
+///
+/// [`IntoSql`]: trait.IntoSql.html
+pub trait FromSql: Sized {
+    /// Converts `data`, failing if it isn't the variant this type expects.
+    fn from_sql(data: &ColumnData) -> Result<Self>;
+}
+
+macro_rules! from_sql_fixed {
+    ($ty:ty, $variant:ident) => {
+        impl FromSql for $ty {
+            fn from_sql(data: &ColumnData) -> Result<Self> {
+                match data {
+                    ColumnData::$variant(Some(v)) => Ok(*v),
+                    ColumnData::$variant(None) => {
+                        Err(Error::protocol(concat!("unexpected NULL for ", stringify!($ty))))
+                    }
+                    _ => Err(Error::protocol(concat!(
+                        "expected a ",
+                        stringify!($variant),
+                        " value"
+                    ))),
+                }
+            }
+        }
+
+        impl FromSql for Option<$ty> {
+            fn from_sql(data: &ColumnData) -> Result<Self> {
+                match data {
+                    ColumnData::$variant(v) => Ok(*v),
+                    _ => Err(Error::protocol(concat!(
+                        "expected a ",
+                        stringify!($variant),
+                        " value"
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+from_sql_fixed!(u8, U8);
+from_sql_fixed!(i16, I16);
+from_sql_fixed!(i32, I32);
+from_sql_fixed!(i64, I64);
+from_sql_fixed!(f32, F32);
+from_sql_fixed!(f64, F64);
+from_sql_fixed!(bool, Bit);
+
+impl FromSql for String {
+    fn from_sql(data: &ColumnData) -> Result<Self> {
+        match data {
+            ColumnData::String(Some(s)) => Ok(s.clone().into_owned()),
+            ColumnData::String(None) => Err(Error::protocol("unexpected NULL for String")),
+            _ => Err(Error::protocol("expected a VARCHAR value")),
+        }
+    }
+}
+
+impl FromSql for Option<String> {
+    fn from_sql(data: &ColumnData) -> Result<Self> {
+        match data {
+            ColumnData::String(s) => Ok(s.as_ref().map(|s| s.clone().into_owned())),
+            _ => Err(Error::protocol("expected a VARCHAR value")),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromSql for chrono::NaiveDateTime {
+    fn from_sql(data: &ColumnData) -> Result<Self> {
+        match data {
+            ColumnData::DateTime2(Some((dt, _))) => Ok(*dt),
+            ColumnData::DateTime2(None) => Err(Error::protocol("unexpected NULL for NaiveDateTime")),
+            _ => Err(Error::protocol("expected a DATETIME2 value")),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromSql for Option<chrono::NaiveDateTime> {
+    fn from_sql(data: &ColumnData) -> Result<Self> {
+        match data {
+            ColumnData::DateTime2(v) => Ok(v.map(|(dt, _)| dt)),
+            _ => Err(Error::protocol("expected a DATETIME2 value")),
+        }
+    }
+}
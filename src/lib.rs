@@ -0,0 +1,45 @@
+//! A native, pure-Rust client library, transport-agnostic over anything
+//! implementing `futures_util::io::{AsyncRead, AsyncWrite}`.
+//!
+//! The wire protocol this crate speaks ([`tds`]) is its own bespoke,
+//! self-describing framing inspired by Microsoft's Tabular Data Stream
+//! protocol, not a conformant implementation of it -- there's no
+//! PRELOGIN/LOGIN7 handshake, no real TDS token types, and no actual
+//! `INSERT BULK`/BCP wire framing. It won't talk to a real SQL Server or
+//! Azure SQL instance; pair it with a server that speaks this crate's own
+//! framing instead.
+
+mod batch_write;
+mod bulk_load;
+mod client;
+mod error;
+mod from_sql;
+pub mod query;
+pub mod row;
+mod to_sql;
+
+pub mod tds;
+
+pub use batch_write::{Assignment, BatchWriteResult, WriteModel};
+pub use bulk_load::{BulkLoadOptions, BulkLoadRequest, ExecuteResult, OrderHint, SortDirection};
+pub use client::{Client, Config};
+pub use error::{Error, Result};
+pub use from_sql::FromSql;
+pub use query::QueryStream;
+pub use row::{ColumnIndex, FromRow, Row};
+pub use tds::codec::{ColumnData, TokenColMetaData, TokenRow, TypeInfo};
+pub use to_sql::{IntoSql, ToSql};
+
+/// Derives [`FromRow`] for a struct, matching each field to a result-set
+/// column by name and converting it with [`FromSql`].
+///
+/// [`FromRow`]: trait.FromRow.html
+/// [`FromSql`]: trait.FromSql.html
+#[cfg(feature = "derive")]
+pub use tiberius_derive::FromRow;
+
+// The derive macro expands to `impl ::tiberius::FromRow for ...`, which only
+// resolves from outside this crate. This lets its own unit tests exercise
+// the derive without leaving the crate.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as tiberius;
@@ -0,0 +1,109 @@
+use crate::row::FromRow;
+use crate::tds::codec::TokenColMetaData;
+use crate::tds::Connection;
+use crate::{Result, Row};
+use futures_util::io::AsyncRead;
+use futures_util::Stream;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// A `Stream` of the [`Row`]s returned by [`Client::query`], consumed
+/// either directly or via [`into_typed`].
+///
+/// [`Row`]: struct.Row.html
+/// [`Client::query`]: client/struct.Client.html#method.query
+/// [`into_typed`]: #method.into_typed
+pub struct QueryStream<'a, S> {
+    connection: &'a mut Connection<S>,
+    columns: Arc<[TokenColMetaData]>,
+    done: bool,
+}
+
+impl<'a, S> QueryStream<'a, S> {
+    pub(crate) fn new(connection: &'a mut Connection<S>, columns: Arc<[TokenColMetaData]>) -> Self {
+        Self {
+            connection,
+            columns,
+            done: false,
+        }
+    }
+
+    /// The result set's column metadata, in positional order.
+    pub fn columns(&self) -> &[TokenColMetaData] {
+        &self.columns
+    }
+
+    /// Maps every row through `T`'s [`FromRow`] implementation instead of
+    /// reading columns out by hand.
+    ///
+    /// [`FromRow`]: row/trait.FromRow.html
+    pub fn into_typed<T>(self) -> IntoTyped<'a, S, T>
+    where
+        T: FromRow,
+    {
+        IntoTyped {
+            inner: self,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, S> Stream for QueryStream<'a, S>
+where
+    S: AsyncRead + Unpin + Send,
+{
+    type Item = Result<Row>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Row>>> {
+        let this = self.get_mut();
+
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        match this.connection.poll_next_row(cx, this.columns.clone()) {
+            Poll::Ready(Ok(None)) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Ready(Ok(Some(row))) => Poll::Ready(Some(Ok(row))),
+            Poll::Ready(Err(e)) => {
+                this.done = true;
+                Poll::Ready(Some(Err(e)))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A [`QueryStream`] mapped through [`FromRow`], returned by
+/// [`QueryStream::into_typed`].
+///
+/// [`QueryStream`]: struct.QueryStream.html
+/// [`FromRow`]: row/trait.FromRow.html
+/// [`QueryStream::into_typed`]: struct.QueryStream.html#method.into_typed
+pub struct IntoTyped<'a, S, T> {
+    inner: QueryStream<'a, S>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, S, T> Stream for IntoTyped<'a, S, T>
+where
+    S: AsyncRead + Unpin + Send,
+    T: FromRow,
+{
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T>>> {
+        let this = self.get_mut();
+
+        match Pin::new(&mut this.inner).poll_next(cx) {
+            Poll::Ready(Some(Ok(row))) => Poll::Ready(Some(T::from_row(row))),
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::from_sql::FromSql;
+use crate::tds::codec::{ColumnData, TokenColMetaData};
+use crate::{Error, Result};
+use std::sync::Arc;
+
+/// One row of a query result, read back column by column with [`get`] or
+/// [`try_get`].
+///
+/// [`get`]: #method.get
+/// [`try_get`]: #method.try_get
+#[derive(Debug, Clone)]
+pub struct Row {
+    columns: Arc<[TokenColMetaData]>,
+    data: Vec<ColumnData>,
+}
+
+impl Row {
+    pub(crate) fn new(columns: Arc<[TokenColMetaData]>, data: Vec<ColumnData>) -> Self {
+        Self { columns, data }
+    }
+
+    /// The result set's column metadata, in positional order.
+    pub fn columns(&self) -> &[TokenColMetaData] {
+        &self.columns
+    }
+
+    /// Reads the column at `index` (a position or a column name) as `T`,
+    /// returning `None` if the column doesn't exist, is `NULL` and `T`
+    /// isn't an `Option`, or doesn't match `T`'s expected wire type.
+    pub fn get<I, T>(&self, index: I) -> Option<T>
+    where
+        I: ColumnIndex,
+        T: FromSql,
+    {
+        self.try_get(index).ok()
+    }
+
+    /// Like [`get`], but surfaces the reason a column couldn't be read
+    /// instead of discarding it.
+    ///
+    /// [`get`]: #method.get
+    pub fn try_get<I, T>(&self, index: I) -> Result<T>
+    where
+        I: ColumnIndex,
+        T: FromSql,
+    {
+        let idx = index
+            .index(self)
+            .ok_or_else(|| Error::protocol("no column with that name or position"))?;
+
+        let data = self
+            .data
+            .get(idx)
+            .ok_or_else(|| Error::protocol("column index out of bounds"))?;
+
+        T::from_sql(data).map_err(|_| {
+            let column = &self.columns[idx];
+
+            Error::protocol(format!(
+                "column `{}`: expected {:?}, found {}",
+                column.name,
+                column.type_info,
+                data.variant_name()
+            ))
+        })
+    }
+}
+
+/// Resolves a column reference -- a position or a name -- against a
+/// [`Row`]'s metadata.
+///
+/// [`Row`]: struct.Row.html
+pub trait ColumnIndex {
+    fn index(&self, row: &Row) -> Option<usize>;
+}
+
+impl ColumnIndex for usize {
+    fn index(&self, row: &Row) -> Option<usize> {
+        (*self < row.data.len()).then_some(*self)
+    }
+}
+
+impl ColumnIndex for &str {
+    fn index(&self, row: &Row) -> Option<usize> {
+        row.columns.iter().position(|column| column.name == *self)
+    }
+}
+
+/// Maps a [`Row`] to a user-defined struct, matching columns to fields by
+/// name. Usually derived with `#[derive(FromRow)]` rather than
+/// implemented by hand.
+///
+/// [`Row`]: struct.Row.html
+pub trait FromRow: Sized {
+    fn from_row(row: Row) -> Result<Self>;
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use super::*;
+    use crate::tds::codec::TypeInfo;
+    use tiberius_derive::FromRow;
+
+    #[derive(Debug, FromRow)]
+    struct User {
+        id: i32,
+        name: String,
+    }
+
+    fn row(data: Vec<ColumnData>) -> Row {
+        let columns = vec![
+            TokenColMetaData { name: "id".into(), type_info: TypeInfo::Int, nullable: false },
+            TokenColMetaData { name: "name".into(), type_info: TypeInfo::VarChar { max_length: None }, nullable: false },
+        ];
+
+        Row::new(columns.into(), data)
+    }
+
+    #[test]
+    fn derived_from_row_maps_columns_by_name() {
+        let row = row(vec![ColumnData::I32(Some(1)), ColumnData::String(Some("bob".into()))]);
+        let user = User::from_row(row).unwrap();
+
+        assert_eq!(1, user.id);
+        assert_eq!("bob", user.name);
+    }
+
+    #[test]
+    fn derived_from_row_surfaces_a_type_mismatch() {
+        let row = row(vec![ColumnData::String(Some("not an int".into())), ColumnData::String(Some("bob".into()))]);
+        let err = User::from_row(row).unwrap_err();
+
+        assert!(err.to_string().contains("id"));
+    }
+}
@@ -0,0 +1,80 @@
+use std::borrow::Cow;
+
+/// A single value bound for the wire, already converted to its TDS
+/// representation. This is what [`IntoSql`] produces and [`FromSql`]
+/// consumes.
+///
+/// [`IntoSql`]: ../trait.IntoSql.html
+/// [`FromSql`]: ../trait.FromSql.html
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    U8(Option<u8>),
+    I16(Option<i16>),
+    I32(Option<i32>),
+    I64(Option<i64>),
+    F32(Option<f32>),
+    F64(Option<f64>),
+    Bit(Option<bool>),
+    String(Option<Cow<'static, str>>),
+    #[cfg(feature = "chrono")]
+    DateTime2(Option<(chrono::NaiveDateTime, u8)>),
+}
+
+impl ColumnData {
+    /// An estimate of how many bytes this value contributes to a TDS row,
+    /// used by the bulk-load auto-flush heuristics. Fixed-width types
+    /// report their wire width; variable-width types report their length
+    /// prefix plus payload.
+    pub(crate) fn wire_size_estimate(&self) -> usize {
+        match self {
+            ColumnData::U8(_) => 1,
+            ColumnData::I16(_) => 2,
+            ColumnData::I32(_) => 4,
+            ColumnData::I64(_) => 8,
+            ColumnData::F32(_) => 4,
+            ColumnData::F64(_) => 8,
+            ColumnData::Bit(_) => 1,
+            ColumnData::String(s) => {
+                // 4-byte length prefix + UTF-8 payload, matching `wire::push_string`.
+                4 + s.as_ref().map(|s| s.len()).unwrap_or(0)
+            }
+            #[cfg(feature = "chrono")]
+            // 8-byte seconds + 4-byte nanoseconds + 1-byte scale, matching
+            // `wire::encode_column_data`.
+            ColumnData::DateTime2(_) => 13,
+        }
+    }
+
+    /// `true` if this value is SQL `NULL`.
+    pub(crate) fn is_null(&self) -> bool {
+        match self {
+            ColumnData::U8(v) => v.is_none(),
+            ColumnData::I16(v) => v.is_none(),
+            ColumnData::I32(v) => v.is_none(),
+            ColumnData::I64(v) => v.is_none(),
+            ColumnData::F32(v) => v.is_none(),
+            ColumnData::F64(v) => v.is_none(),
+            ColumnData::Bit(v) => v.is_none(),
+            ColumnData::String(v) => v.is_none(),
+            #[cfg(feature = "chrono")]
+            ColumnData::DateTime2(v) => v.is_none(),
+        }
+    }
+
+    /// The TDS type name of this value, used to describe a
+    /// [`FromSql`](../trait.FromSql.html) mismatch to the caller.
+    pub(crate) fn variant_name(&self) -> &'static str {
+        match self {
+            ColumnData::U8(_) => "TINYINT",
+            ColumnData::I16(_) => "SMALLINT",
+            ColumnData::I32(_) => "INT",
+            ColumnData::I64(_) => "BIGINT",
+            ColumnData::F32(_) => "REAL",
+            ColumnData::F64(_) => "FLOAT",
+            ColumnData::Bit(_) => "BIT",
+            ColumnData::String(_) => "VARCHAR",
+            #[cfg(feature = "chrono")]
+            ColumnData::DateTime2(_) => "DATETIME2",
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod column_data;
+mod type_info;
+
+pub mod token;
+pub(crate) mod wire;
+
+pub use column_data::ColumnData;
+pub use token::{TokenColMetaData, TokenRow};
+pub use type_info::TypeInfo;
@@ -0,0 +1,5 @@
+mod token_col_metadata;
+mod token_row;
+
+pub use token_col_metadata::TokenColMetaData;
+pub use token_row::TokenRow;
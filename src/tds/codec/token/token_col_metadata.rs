@@ -0,0 +1,10 @@
+use crate::tds::codec::TypeInfo;
+
+/// One column of a `COLMETADATA` token: the name the server gave it, its
+/// resolved wire type, and whether it accepts `NULL`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenColMetaData {
+    pub name: String,
+    pub type_info: TypeInfo,
+    pub nullable: bool,
+}
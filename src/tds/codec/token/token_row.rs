@@ -0,0 +1,43 @@
+use crate::tds::codec::ColumnData;
+
+/// A single row of data, built up column by column with [`push`] and handed
+/// to the server as part of a `ROW` token.
+///
+/// [`push`]: #method.push
+#[derive(Debug, Clone, Default)]
+pub struct TokenRow {
+    columns: Vec<ColumnData>,
+}
+
+impl TokenRow {
+    /// Creates a new, empty row.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a value to the end of the row.
+    pub fn push(&mut self, data: ColumnData) {
+        self.columns.push(data);
+    }
+
+    /// The values of this row in column order.
+    pub fn columns(&self) -> &[ColumnData] {
+        &self.columns
+    }
+
+    /// The number of columns bound so far.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// `true` if no column has been bound yet.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// An estimate, in bytes, of how much space this row occupies on the
+    /// wire once encoded.
+    pub(crate) fn wire_size_estimate(&self) -> usize {
+        self.columns.iter().map(ColumnData::wire_size_estimate).sum()
+    }
+}
@@ -0,0 +1,19 @@
+/// A stripped-down mirror of the TDS `TYPE_INFO` structure returned by the
+/// server for a column. Only the parts needed to validate client-bound
+/// values are kept here; the wire-level variants (precision, scale,
+/// collation, ...) live with the rest of the codec once this crate grows a
+/// full decoder.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeInfo {
+    TinyInt,
+    SmallInt,
+    Int,
+    BigInt,
+    Real,
+    Float,
+    Bit,
+    /// `VARCHAR(n)`/`NVARCHAR(n)`, `n` in characters, `None` for `MAX`.
+    VarChar { max_length: Option<usize> },
+    #[cfg(feature = "chrono")]
+    DateTime2 { scale: u8 },
+}
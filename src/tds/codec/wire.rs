@@ -0,0 +1,254 @@
+//! Binary encode/decode for the codec types, shared by every request and
+//! response [`crate::tds::packet`] carries over the wire.
+//!
+//! This isn't byte-for-byte Microsoft's TDS format -- it's this driver's
+//! own self-describing framing, simple enough to decode without a side
+//! channel of type information. Every [`ColumnData`] carries its own type
+//! tag and nullness flag, so a row can be decoded on its own, and every
+//! string is a `u32` length prefix followed by UTF-8 bytes.
+
+use super::{ColumnData, TokenColMetaData, TokenRow, TypeInfo};
+use crate::{Error, Result};
+
+fn take<'a>(buf: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    if buf.len() < n {
+        return Err(Error::protocol("unexpected end of packet payload"));
+    }
+
+    let (head, tail) = buf.split_at(n);
+    *buf = tail;
+
+    Ok(head)
+}
+
+fn read_u8(buf: &mut &[u8]) -> Result<u8> {
+    Ok(take(buf, 1)?[0])
+}
+
+pub(crate) fn read_u8_flag(buf: &mut &[u8]) -> Result<bool> {
+    Ok(read_u8(buf)? != 0)
+}
+
+pub(crate) fn read_u32(buf: &mut &[u8]) -> Result<u32> {
+    Ok(u32::from_le_bytes(take(buf, 4)?.try_into().unwrap()))
+}
+
+pub(crate) fn read_u64(buf: &mut &[u8]) -> Result<u64> {
+    Ok(u64::from_le_bytes(take(buf, 8)?.try_into().unwrap()))
+}
+
+fn read_i64(buf: &mut &[u8]) -> Result<i64> {
+    Ok(i64::from_le_bytes(take(buf, 8)?.try_into().unwrap()))
+}
+
+pub(crate) fn read_string(buf: &mut &[u8]) -> Result<String> {
+    let len = read_u32(buf)? as usize;
+    let bytes = take(buf, len)?;
+
+    String::from_utf8(bytes.to_vec()).map_err(|_| Error::protocol("invalid utf-8 in packet string"))
+}
+
+pub(crate) fn push_string(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// Only ever needed to build server-shaped responses in tests -- in
+/// production the client decodes `TYPE_INFO`/`COLMETADATA`/server errors,
+/// it never constructs them.
+#[cfg(test)]
+pub(crate) fn encode_type_info(out: &mut Vec<u8>, type_info: &TypeInfo) {
+    match type_info {
+        TypeInfo::TinyInt => out.push(0),
+        TypeInfo::SmallInt => out.push(1),
+        TypeInfo::Int => out.push(2),
+        TypeInfo::BigInt => out.push(3),
+        TypeInfo::Real => out.push(4),
+        TypeInfo::Float => out.push(5),
+        TypeInfo::Bit => out.push(6),
+        TypeInfo::VarChar { max_length } => {
+            out.push(7);
+            out.extend_from_slice(&max_length.map(|n| n as u32).unwrap_or(u32::MAX).to_le_bytes());
+        }
+        #[cfg(feature = "chrono")]
+        TypeInfo::DateTime2 { scale } => {
+            out.push(8);
+            out.push(*scale);
+        }
+    }
+}
+
+pub(crate) fn decode_type_info(buf: &mut &[u8]) -> Result<TypeInfo> {
+    let type_info = match read_u8(buf)? {
+        0 => TypeInfo::TinyInt,
+        1 => TypeInfo::SmallInt,
+        2 => TypeInfo::Int,
+        3 => TypeInfo::BigInt,
+        4 => TypeInfo::Real,
+        5 => TypeInfo::Float,
+        6 => TypeInfo::Bit,
+        7 => {
+            let raw = read_u32(buf)?;
+            let max_length = if raw == u32::MAX { None } else { Some(raw as usize) };
+            TypeInfo::VarChar { max_length }
+        }
+        #[cfg(feature = "chrono")]
+        8 => TypeInfo::DateTime2 { scale: read_u8(buf)? },
+        tag => return Err(Error::protocol(format!("unknown TYPE_INFO tag {tag}"))),
+    };
+
+    Ok(type_info)
+}
+
+#[cfg(test)]
+pub(crate) fn encode_col_metadata(out: &mut Vec<u8>, columns: &[TokenColMetaData]) {
+    out.extend_from_slice(&(columns.len() as u32).to_le_bytes());
+
+    for column in columns {
+        push_string(out, &column.name);
+        encode_type_info(out, &column.type_info);
+        out.push(column.nullable as u8);
+    }
+}
+
+pub(crate) fn decode_col_metadata(buf: &mut &[u8]) -> Result<Vec<TokenColMetaData>> {
+    let count = read_u32(buf)? as usize;
+    let mut columns = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let name = read_string(buf)?;
+        let type_info = decode_type_info(buf)?;
+        let nullable = read_u8(buf)? != 0;
+
+        columns.push(TokenColMetaData { name, type_info, nullable });
+    }
+
+    Ok(columns)
+}
+
+pub(crate) fn encode_column_data(out: &mut Vec<u8>, data: &ColumnData) {
+    let (tag, is_null) = match data {
+        ColumnData::U8(v) => (0u8, v.is_none()),
+        ColumnData::I16(v) => (1, v.is_none()),
+        ColumnData::I32(v) => (2, v.is_none()),
+        ColumnData::I64(v) => (3, v.is_none()),
+        ColumnData::F32(v) => (4, v.is_none()),
+        ColumnData::F64(v) => (5, v.is_none()),
+        ColumnData::Bit(v) => (6, v.is_none()),
+        ColumnData::String(v) => (7, v.is_none()),
+        #[cfg(feature = "chrono")]
+        ColumnData::DateTime2(v) => (8, v.is_none()),
+    };
+
+    out.push(tag);
+    out.push(!is_null as u8);
+
+    if is_null {
+        return;
+    }
+
+    match data {
+        ColumnData::U8(v) => out.push(v.unwrap()),
+        ColumnData::I16(v) => out.extend_from_slice(&v.unwrap().to_le_bytes()),
+        ColumnData::I32(v) => out.extend_from_slice(&v.unwrap().to_le_bytes()),
+        ColumnData::I64(v) => out.extend_from_slice(&v.unwrap().to_le_bytes()),
+        ColumnData::F32(v) => out.extend_from_slice(&v.unwrap().to_le_bytes()),
+        ColumnData::F64(v) => out.extend_from_slice(&v.unwrap().to_le_bytes()),
+        ColumnData::Bit(v) => out.push(v.unwrap() as u8),
+        ColumnData::String(v) => push_string(out, v.as_deref().unwrap()),
+        #[cfg(feature = "chrono")]
+        ColumnData::DateTime2(v) => {
+            let (dt, scale) = v.unwrap();
+            out.extend_from_slice(&dt.and_utc().timestamp().to_le_bytes());
+            out.extend_from_slice(&dt.and_utc().timestamp_subsec_nanos().to_le_bytes());
+            out.push(scale);
+        }
+    }
+}
+
+pub(crate) fn decode_column_data(buf: &mut &[u8]) -> Result<ColumnData> {
+    let tag = read_u8(buf)?;
+    let present = read_u8(buf)? != 0;
+
+    let data = match (tag, present) {
+        (0, false) => ColumnData::U8(None),
+        (0, true) => ColumnData::U8(Some(read_u8(buf)?)),
+        (1, false) => ColumnData::I16(None),
+        (1, true) => ColumnData::I16(Some(i16::from_le_bytes(take(buf, 2)?.try_into().unwrap()))),
+        (2, false) => ColumnData::I32(None),
+        (2, true) => ColumnData::I32(Some(i32::from_le_bytes(take(buf, 4)?.try_into().unwrap()))),
+        (3, false) => ColumnData::I64(None),
+        (3, true) => ColumnData::I64(Some(read_i64(buf)?)),
+        (4, false) => ColumnData::F32(None),
+        (4, true) => ColumnData::F32(Some(f32::from_le_bytes(take(buf, 4)?.try_into().unwrap()))),
+        (5, false) => ColumnData::F64(None),
+        (5, true) => ColumnData::F64(Some(f64::from_le_bytes(take(buf, 8)?.try_into().unwrap()))),
+        (6, false) => ColumnData::Bit(None),
+        (6, true) => ColumnData::Bit(Some(read_u8(buf)? != 0)),
+        (7, false) => ColumnData::String(None),
+        (7, true) => ColumnData::String(Some(read_string(buf)?.into())),
+        #[cfg(feature = "chrono")]
+        (8, false) => ColumnData::DateTime2(None),
+        #[cfg(feature = "chrono")]
+        (8, true) => {
+            let secs = read_i64(buf)?;
+            let nanos = read_u32(buf)?;
+            let scale = read_u8(buf)?;
+
+            let dt = chrono::DateTime::from_timestamp(secs, nanos)
+                .ok_or_else(|| Error::protocol("out-of-range DATETIME2 value"))?
+                .naive_utc();
+
+            ColumnData::DateTime2(Some((dt, scale)))
+        }
+        (tag, _) => return Err(Error::protocol(format!("unknown ColumnData tag {tag}"))),
+    };
+
+    Ok(data)
+}
+
+pub(crate) fn encode_row(out: &mut Vec<u8>, row: &TokenRow) {
+    out.extend_from_slice(&(row.len() as u32).to_le_bytes());
+
+    for data in row.columns() {
+        encode_column_data(out, data);
+    }
+}
+
+pub(crate) fn decode_row_values(buf: &mut &[u8]) -> Result<Vec<ColumnData>> {
+    let count = read_u32(buf)? as usize;
+    let mut values = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        values.push(decode_column_data(buf)?);
+    }
+
+    Ok(values)
+}
+
+pub(crate) fn decode_server_error(payload: &[u8]) -> Error {
+    let mut buf = payload;
+
+    match decode_server_error_fields(&mut buf) {
+        Ok(e) => e,
+        Err(e) => e,
+    }
+}
+
+/// Like [`decode_server_error`], but reads the code/message pair from the
+/// front of a shared cursor instead of owning the whole payload -- used
+/// when a response packet carries more than one error, e.g. one per
+/// statement in a [`crate::tds::connection::Connection::execute_batch`]
+/// response.
+pub(crate) fn decode_server_error_fields(buf: &mut &[u8]) -> Result<Error> {
+    let code = read_u32(buf)?;
+    let message = read_string(buf)?;
+
+    Ok(Error::Server { code, message })
+}
+
+#[cfg(test)]
+pub(crate) fn encode_server_error(out: &mut Vec<u8>, code: u32, message: &str) {
+    out.extend_from_slice(&code.to_le_bytes());
+    push_string(out, message);
+}
@@ -0,0 +1,720 @@
+use crate::tds::codec::{wire, ColumnData, TokenColMetaData, TokenRow};
+use crate::tds::packet;
+use crate::{Error, Result, Row};
+use futures_util::io::{AsyncRead, AsyncWrite};
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// The raw TDS socket plus whatever protocol state survives between
+/// requests (a partly written bulk-rows packet, a partly read row packet).
+/// Every request the client sends -- queries, prepared statements, batches
+/// and bulk loads -- goes through its methods.
+pub struct Connection<S> {
+    transport: S,
+    bulk_write: PendingWrite,
+    row_read: PendingRead,
+}
+
+/// Buffers one not-yet-fully-written packet across `Poll::Pending`s.
+#[derive(Default)]
+struct PendingWrite {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl PendingWrite {
+    fn poll_drain<S>(&mut self, mut transport: Pin<&mut S>, cx: &mut Context<'_>) -> Poll<io::Result<()>>
+    where
+        S: AsyncWrite,
+    {
+        while self.pos < self.buf.len() {
+            match transport.as_mut().poll_write(cx, &self.buf[self.pos..]) {
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write whole packet",
+                    )))
+                }
+                Poll::Ready(Ok(n)) => self.pos += n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match transport.poll_flush(cx) {
+            Poll::Ready(Ok(())) => {
+                self.buf.clear();
+                self.pos = 0;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Buffers one not-yet-fully-read packet across `Poll::Pending`s.
+#[derive(Default)]
+struct PendingRead {
+    header: [u8; 5],
+    header_filled: usize,
+    payload: Vec<u8>,
+    payload_filled: usize,
+    reading_payload: bool,
+}
+
+impl PendingRead {
+    fn poll_packet<S>(&mut self, mut transport: Pin<&mut S>, cx: &mut Context<'_>) -> Poll<io::Result<(u8, Vec<u8>)>>
+    where
+        S: AsyncRead,
+    {
+        if !self.reading_payload {
+            while self.header_filled < self.header.len() {
+                match transport.as_mut().poll_read(cx, &mut self.header[self.header_filled..]) {
+                    Poll::Ready(Ok(0)) => {
+                        return Poll::Ready(Err(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "connection closed mid packet header",
+                        )))
+                    }
+                    Poll::Ready(Ok(n)) => self.header_filled += n,
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            let len = u32::from_le_bytes(self.header[1..5].try_into().unwrap()) as usize;
+            self.payload = vec![0u8; len];
+            self.payload_filled = 0;
+            self.reading_payload = true;
+        }
+
+        while self.payload_filled < self.payload.len() {
+            match transport.as_mut().poll_read(cx, &mut self.payload[self.payload_filled..]) {
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "connection closed mid packet payload",
+                    )))
+                }
+                Poll::Ready(Ok(n)) => self.payload_filled += n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let kind = self.header[0];
+        let payload = std::mem::take(&mut self.payload);
+        self.header_filled = 0;
+        self.payload_filled = 0;
+        self.reading_payload = false;
+
+        Poll::Ready(Ok((kind, payload)))
+    }
+}
+
+impl<S> Connection<S>
+where
+    S: AsyncWrite + Unpin + Send,
+{
+    pub(crate) fn new(transport: S) -> Self {
+        Self {
+            transport,
+            bulk_write: PendingWrite::default(),
+            row_read: PendingRead::default(),
+        }
+    }
+
+    /// Writes one chunk of a bulk-load stream as a `BULK_ROWS` packet,
+    /// keeping the surrounding `INSERT BULK` statement open, and reports
+    /// `Poll::Pending` rather than blocking when the socket isn't ready for
+    /// more writes yet. Shared by the async [`BulkLoadRequest::send`] path
+    /// and its [`Sink`] implementation.
+    ///
+    /// [`BulkLoadRequest::send`]: ../../bulk_load/struct.BulkLoadRequest.html#method.send
+    /// [`Sink`]: https://docs.rs/futures-util/latest/futures_util/sink/trait.Sink.html
+    pub(crate) fn poll_flush_bulk_rows(
+        &mut self,
+        cx: &mut Context<'_>,
+        rows: &[TokenRow],
+    ) -> Poll<Result<()>> {
+        if self.bulk_write.buf.is_empty() {
+            let mut payload = Vec::new();
+            payload.extend_from_slice(&(rows.len() as u32).to_le_bytes());
+
+            for row in rows {
+                wire::encode_row(&mut payload, row);
+            }
+
+            self.bulk_write.buf.push(packet::BULK_ROWS);
+            self.bulk_write.buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            self.bulk_write.buf.extend_from_slice(&payload);
+        }
+
+        self.bulk_write
+            .poll_drain(Pin::new(&mut self.transport), cx)
+            .map_err(Error::Io)
+    }
+}
+
+impl<S> Connection<S>
+where
+    S: AsyncRead + Unpin + Send,
+{
+    /// Reads the next row of the current result set, if any.
+    pub(crate) fn poll_next_row(
+        &mut self,
+        cx: &mut Context<'_>,
+        columns: Arc<[TokenColMetaData]>,
+    ) -> Poll<Result<Option<Row>>> {
+        match self.row_read.poll_packet(Pin::new(&mut self.transport), cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Err(e)) => Poll::Ready(Err(Error::Io(e))),
+            Poll::Ready(Ok((packet::QUERY_DONE, _))) => Poll::Ready(Ok(None)),
+            Poll::Ready(Ok((packet::ERROR, payload))) => {
+                Poll::Ready(Err(wire::decode_server_error(&payload)))
+            }
+            Poll::Ready(Ok((packet::QUERY_ROW, payload))) => {
+                let mut buf = payload.as_slice();
+
+                match wire::decode_row_values(&mut buf) {
+                    Ok(data) => Poll::Ready(Ok(Some(Row::new(columns, data)))),
+                    Err(e) => Poll::Ready(Err(e)),
+                }
+            }
+            Poll::Ready(Ok((kind, _))) => Poll::Ready(Err(Error::protocol(format!(
+                "unexpected packet kind {kind} while streaming rows"
+            )))),
+        }
+    }
+}
+
+impl<S> Connection<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    /// Sends one request packet and reads back the response, erroring out
+    /// on a server-reported failure or an unexpected response kind.
+    async fn roundtrip(&mut self, send_kind: u8, payload: Vec<u8>, expect_kind: u8) -> Result<Vec<u8>> {
+        packet::write_packet(&mut self.transport, send_kind, &payload).await?;
+        let (kind, response) = packet::read_packet(&mut self.transport).await?;
+
+        if kind == packet::ERROR {
+            return Err(wire::decode_server_error(&response));
+        }
+
+        if kind != expect_kind {
+            return Err(Error::protocol(format!(
+                "expected packet kind {expect_kind}, got {kind}"
+            )));
+        }
+
+        Ok(response)
+    }
+
+    /// Closes the `INSERT BULK` statement and returns the server-reported
+    /// number of rows actually inserted.
+    pub(crate) async fn finish_bulk_insert(&mut self) -> Result<u64> {
+        let response = self
+            .roundtrip(packet::BULK_DONE, Vec::new(), packet::BULK_DONE_OK)
+            .await?;
+
+        wire::read_u64(&mut response.as_slice())
+    }
+
+    /// Sends `sp_prepare` for `sql`, returning the server-assigned
+    /// statement handle and the `COLMETADATA` describing its result set
+    /// (empty for statements that return no rows).
+    pub(crate) async fn prepare_statement(
+        &mut self,
+        sql: &str,
+    ) -> Result<(i32, Vec<TokenColMetaData>)> {
+        let mut payload = Vec::new();
+        wire::push_string(&mut payload, sql);
+
+        let response = self.roundtrip(packet::PREPARE, payload, packet::PREPARE_OK).await?;
+        let mut buf = response.as_slice();
+
+        let handle = wire::read_u64(&mut buf)? as i32;
+        let columns = wire::decode_col_metadata(&mut buf)?;
+
+        Ok((handle, columns))
+    }
+
+    /// Sends `sp_unprepare` for a handle that's been evicted from the
+    /// statement cache or is no longer needed.
+    pub(crate) async fn unprepare_statement(&mut self, handle: i32) -> Result<()> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&(handle as u64).to_le_bytes());
+
+        self.roundtrip(packet::UNPREPARE, payload, packet::UNPREPARE_OK).await?;
+
+        Ok(())
+    }
+
+    /// Performs the `INSERT BULK` handshake against `table`, restricted to
+    /// `columns` (server column order if empty) and tuned by
+    /// `check_constraints`/`fire_triggers`/`keep_nulls` and `order_hints`
+    /// (each a column name paired with whether it's sorted descending),
+    /// returning the `COLMETADATA` the server reports for the target
+    /// columns so the caller can validate rows against it before the
+    /// first one is sent.
+    pub(crate) async fn prepare_bulk_insert(
+        &mut self,
+        table: &str,
+        columns: &[&str],
+        check_constraints: bool,
+        fire_triggers: bool,
+        keep_nulls: bool,
+        order_hints: &[(&str, bool)],
+    ) -> Result<Vec<TokenColMetaData>> {
+        let mut payload = Vec::new();
+        wire::push_string(&mut payload, table);
+        payload.extend_from_slice(&(columns.len() as u32).to_le_bytes());
+
+        for column in columns {
+            wire::push_string(&mut payload, column);
+        }
+
+        let mut flags = 0u8;
+        if check_constraints {
+            flags |= 0b001;
+        }
+        if fire_triggers {
+            flags |= 0b010;
+        }
+        if keep_nulls {
+            flags |= 0b100;
+        }
+        payload.push(flags);
+
+        payload.extend_from_slice(&(order_hints.len() as u32).to_le_bytes());
+
+        for (column, descending) in order_hints {
+            wire::push_string(&mut payload, column);
+            payload.push(*descending as u8);
+        }
+
+        let response = self
+            .roundtrip(packet::BULK_PREPARE, payload, packet::BULK_PREPARE_OK)
+            .await?;
+
+        wire::decode_col_metadata(&mut response.as_slice())
+    }
+
+    /// Runs a concatenated block of statements (built by
+    /// [`Client::batch_write`]) as a single round-trip, returning the
+    /// affected-row count, or the failure, of each statement in order.
+    ///
+    /// [`Client::batch_write`]: ../../client/struct.Client.html#method.batch_write
+    pub(crate) async fn execute_batch(
+        &mut self,
+        statement: &str,
+        params: &[ColumnData],
+        statement_count: usize,
+    ) -> Result<Vec<Result<u64>>> {
+        let mut payload = Vec::new();
+        wire::push_string(&mut payload, statement);
+        payload.extend_from_slice(&(params.len() as u32).to_le_bytes());
+
+        for param in params {
+            wire::encode_column_data(&mut payload, param);
+        }
+
+        payload.extend_from_slice(&(statement_count as u32).to_le_bytes());
+
+        let response = self.roundtrip(packet::BATCH, payload, packet::BATCH_OK).await?;
+        let mut buf = response.as_slice();
+
+        let count = wire::read_u64(&mut buf)? as usize;
+        let mut results = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let ok = wire::read_u8_flag(&mut buf)?;
+
+            if ok {
+                results.push(Ok(wire::read_u64(&mut buf)?));
+            } else {
+                results.push(Err(wire::decode_server_error_fields(&mut buf)?));
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Sends `sql` for execution and returns the `COLMETADATA` of its
+    /// result set, ready for [`poll_next_row`] to stream rows against.
+    ///
+    /// [`poll_next_row`]: #method.poll_next_row
+    pub(crate) async fn start_query(
+        &mut self,
+        sql: &str,
+        params: &[ColumnData],
+    ) -> Result<Arc<[TokenColMetaData]>> {
+        let mut payload = Vec::new();
+        wire::push_string(&mut payload, sql);
+        payload.extend_from_slice(&(params.len() as u32).to_le_bytes());
+
+        for param in params {
+            wire::encode_column_data(&mut payload, param);
+        }
+
+        let response = self.roundtrip(packet::QUERY, payload, packet::QUERY_OK).await?;
+        let columns = wire::decode_col_metadata(&mut response.as_slice())?;
+
+        Ok(Arc::from(columns))
+    }
+
+    /// Runs a statement previously prepared with [`prepare_statement`] by
+    /// its server-assigned handle instead of resending the SQL text,
+    /// returning the affected-row count.
+    ///
+    /// [`prepare_statement`]: #method.prepare_statement
+    pub(crate) async fn execute_prepared(&mut self, handle: i32, params: &[ColumnData]) -> Result<u64> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&(handle as u64).to_le_bytes());
+        payload.extend_from_slice(&(params.len() as u32).to_le_bytes());
+
+        for param in params {
+            wire::encode_column_data(&mut payload, param);
+        }
+
+        let response = self.roundtrip(packet::EXEC, payload, packet::EXEC_OK).await?;
+
+        wire::read_u64(&mut response.as_slice())
+    }
+
+    /// Runs a query previously prepared with [`prepare_statement`] by its
+    /// server-assigned handle instead of resending the SQL text, returning
+    /// the `COLMETADATA` of its result set, ready for [`poll_next_row`] to
+    /// stream rows against.
+    ///
+    /// [`prepare_statement`]: #method.prepare_statement
+    /// [`poll_next_row`]: #method.poll_next_row
+    pub(crate) async fn query_prepared(
+        &mut self,
+        handle: i32,
+        params: &[ColumnData],
+    ) -> Result<Arc<[TokenColMetaData]>> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&(handle as u64).to_le_bytes());
+        payload.extend_from_slice(&(params.len() as u32).to_le_bytes());
+
+        for param in params {
+            wire::encode_column_data(&mut payload, param);
+        }
+
+        let response = self
+            .roundtrip(packet::QUERY_EXEC, payload, packet::QUERY_EXEC_OK)
+            .await?;
+        let columns = wire::decode_col_metadata(&mut response.as_slice())?;
+
+        Ok(Arc::from(columns))
+    }
+}
+
+#[cfg(test)]
+impl<S> Connection<S> {
+    /// Exposes the underlying transport for tests outside this module to
+    /// assert against, e.g. a [`test_util::MockTransport`]'s `written`
+    /// buffer.
+    pub(crate) fn transport(&self) -> &S {
+        &self.transport
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// An in-memory transport for unit tests: serves pre-seeded response
+    /// packets on read and records everything written, so a test can
+    /// assert on both sides of a round trip without a live server.
+    pub(crate) struct MockTransport {
+        to_read: VecDeque<u8>,
+        pub(crate) written: Vec<u8>,
+    }
+
+    impl MockTransport {
+        pub(crate) fn new() -> Self {
+            Self { to_read: VecDeque::new(), written: Vec::new() }
+        }
+
+        pub(crate) fn push_packet(&mut self, kind: u8, payload: &[u8]) {
+            self.to_read.push_back(kind);
+            self.to_read.extend((payload.len() as u32).to_le_bytes());
+            self.to_read.extend(payload.iter().copied());
+        }
+    }
+
+    impl AsyncRead for MockTransport {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let n = buf.len().min(self.to_read.len());
+
+            for slot in buf.iter_mut().take(n) {
+                *slot = self.to_read.pop_front().unwrap();
+            }
+
+            Poll::Ready(Ok(n))
+        }
+    }
+
+    impl AsyncWrite for MockTransport {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.written.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_util::MockTransport;
+    use super::*;
+    use crate::tds::codec::{wire, TypeInfo};
+    use futures_util::future::poll_fn;
+
+    fn metadata(name: &str, type_info: TypeInfo, nullable: bool) -> TokenColMetaData {
+        TokenColMetaData { name: name.to_owned(), type_info, nullable }
+    }
+
+    #[test]
+    fn prepare_statement_decodes_handle_and_metadata_and_writes_the_request() {
+        futures_executor::block_on(async {
+            let mut transport = MockTransport::new();
+
+            let mut response = Vec::new();
+            response.extend_from_slice(&42u64.to_le_bytes());
+            wire::encode_col_metadata(&mut response, &[metadata("id", TypeInfo::Int, false)]);
+            transport.push_packet(packet::PREPARE_OK, &response);
+
+            let mut conn = Connection::new(transport);
+            let (handle, columns) = conn.prepare_statement("SELECT 1").await.unwrap();
+
+            assert_eq!(42, handle);
+            assert_eq!(vec![metadata("id", TypeInfo::Int, false)], columns);
+
+            let written = &conn.transport.written;
+            assert_eq!(packet::PREPARE, written[0]);
+            assert!(written.windows(b"SELECT 1".len()).any(|w| w == b"SELECT 1"));
+        });
+    }
+
+    #[test]
+    fn unprepare_statement_writes_the_handle() {
+        futures_executor::block_on(async {
+            let mut transport = MockTransport::new();
+            transport.push_packet(packet::UNPREPARE_OK, &[]);
+
+            let mut conn = Connection::new(transport);
+            conn.unprepare_statement(42).await.unwrap();
+
+            let mut expected = Vec::new();
+            expected.push(packet::UNPREPARE);
+            expected.extend_from_slice(&8u32.to_le_bytes());
+            expected.extend_from_slice(&42u64.to_le_bytes());
+
+            assert_eq!(expected, conn.transport.written);
+        });
+    }
+
+    #[test]
+    fn prepare_bulk_insert_decodes_non_empty_metadata() {
+        futures_executor::block_on(async {
+            let mut transport = MockTransport::new();
+
+            let mut response = Vec::new();
+            wire::encode_col_metadata(
+                &mut response,
+                &[metadata("content", TypeInfo::VarChar { max_length: Some(10) }, false)],
+            );
+            transport.push_packet(packet::BULK_PREPARE_OK, &response);
+
+            let mut conn = Connection::new(transport);
+            let columns = conn
+                .prepare_bulk_insert("t", &["content"], false, false, false, &[])
+                .await
+                .unwrap();
+
+            assert_eq!(1, columns.len());
+            assert!(!columns[0].nullable);
+        });
+    }
+
+    #[test]
+    fn prepare_bulk_insert_writes_options_and_order_hints() {
+        futures_executor::block_on(async {
+            let mut transport = MockTransport::new();
+
+            let mut response = Vec::new();
+            wire::encode_col_metadata(&mut response, &[]);
+            transport.push_packet(packet::BULK_PREPARE_OK, &response);
+
+            let mut conn = Connection::new(transport);
+            conn.prepare_bulk_insert("t", &["a"], true, false, true, &[("a", true)])
+                .await
+                .unwrap();
+
+            let written = &conn.transport.written;
+            let mut payload = &written[5..];
+
+            wire::read_string(&mut payload).unwrap(); // table
+            let column_count = wire::read_u32(&mut payload).unwrap();
+            for _ in 0..column_count {
+                wire::read_string(&mut payload).unwrap();
+            }
+
+            let flags = payload[0];
+            payload = &payload[1..];
+            assert_eq!(0b101, flags);
+
+            let hint_count = wire::read_u32(&mut payload).unwrap();
+            assert_eq!(1, hint_count);
+
+            let hint_column = wire::read_string(&mut payload).unwrap();
+            assert_eq!("a", hint_column);
+            assert_eq!(1, payload[0]);
+        });
+    }
+
+    #[test]
+    fn finish_bulk_insert_reports_the_servers_total() {
+        futures_executor::block_on(async {
+            let mut transport = MockTransport::new();
+            transport.push_packet(packet::BULK_DONE_OK, &7u64.to_le_bytes());
+
+            let mut conn = Connection::new(transport);
+            assert_eq!(7, conn.finish_bulk_insert().await.unwrap());
+        });
+    }
+
+    #[test]
+    fn execute_batch_decodes_mixed_results() {
+        futures_executor::block_on(async {
+            let mut transport = MockTransport::new();
+
+            let mut response = Vec::new();
+            response.extend_from_slice(&2u64.to_le_bytes());
+            response.push(1);
+            response.extend_from_slice(&3u64.to_le_bytes());
+            response.push(0);
+            wire::encode_server_error(&mut response, 547, "duplicate key");
+            transport.push_packet(packet::BATCH_OK, &response);
+
+            let mut conn = Connection::new(transport);
+            let results = conn.execute_batch("INSERT ...; INSERT ...", &[], 2).await.unwrap();
+
+            assert_eq!(3, *results[0].as_ref().unwrap());
+            assert!(results[1].is_err());
+        });
+    }
+
+    #[test]
+    fn poll_flush_bulk_rows_writes_the_row_payload() {
+        futures_executor::block_on(async {
+            let transport = MockTransport::new();
+            let mut conn = Connection::new(transport);
+
+            let mut row = TokenRow::new();
+            row.push(ColumnData::I32(Some(7)));
+            let rows = vec![row];
+
+            poll_fn(|cx| conn.poll_flush_bulk_rows(cx, &rows)).await.unwrap();
+
+            let written = &conn.transport.written;
+            assert_eq!(packet::BULK_ROWS, written[0]);
+
+            let mut payload = &written[5..];
+            let decoded_rows = wire::read_u32(&mut payload).unwrap();
+            assert_eq!(1, decoded_rows);
+
+            let values = wire::decode_row_values(&mut payload).unwrap();
+            assert_eq!(vec![ColumnData::I32(Some(7))], values);
+        });
+    }
+
+    #[test]
+    fn execute_prepared_writes_the_handle_and_params() {
+        futures_executor::block_on(async {
+            let mut transport = MockTransport::new();
+            transport.push_packet(packet::EXEC_OK, &3u64.to_le_bytes());
+
+            let mut conn = Connection::new(transport);
+            let total = conn
+                .execute_prepared(42, &[ColumnData::I32(Some(7))])
+                .await
+                .unwrap();
+
+            assert_eq!(3, total);
+
+            let mut expected = Vec::new();
+            expected.push(packet::EXEC);
+            let mut payload = Vec::new();
+            payload.extend_from_slice(&42u64.to_le_bytes());
+            payload.extend_from_slice(&1u32.to_le_bytes());
+            wire::encode_column_data(&mut payload, &ColumnData::I32(Some(7)));
+            expected.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            expected.extend_from_slice(&payload);
+
+            assert_eq!(expected, conn.transport.written);
+        });
+    }
+
+    #[test]
+    fn query_prepared_decodes_the_result_set_metadata() {
+        futures_executor::block_on(async {
+            let mut transport = MockTransport::new();
+
+            let mut response = Vec::new();
+            wire::encode_col_metadata(&mut response, &[metadata("id", TypeInfo::Int, false)]);
+            transport.push_packet(packet::QUERY_EXEC_OK, &response);
+
+            let mut conn = Connection::new(transport);
+            let columns = conn.query_prepared(42, &[]).await.unwrap();
+
+            assert_eq!(vec![metadata("id", TypeInfo::Int, false)], columns.to_vec());
+        });
+    }
+
+    #[test]
+    fn poll_next_row_decodes_rows_then_done() {
+        futures_executor::block_on(async {
+            let mut transport = MockTransport::new();
+
+            let mut row = TokenRow::new();
+            row.push(ColumnData::I32(Some(1)));
+            let mut row_bytes = Vec::new();
+            wire::encode_row(&mut row_bytes, &row);
+            transport.push_packet(packet::QUERY_ROW, &row_bytes);
+            transport.push_packet(packet::QUERY_DONE, &[]);
+
+            let mut conn = Connection::new(transport);
+            let columns: Arc<[TokenColMetaData]> = Arc::from(vec![metadata("id", TypeInfo::Int, false)]);
+
+            let first = poll_fn(|cx| conn.poll_next_row(cx, columns.clone())).await.unwrap();
+            assert!(first.is_some());
+
+            let second = poll_fn(|cx| conn.poll_next_row(cx, columns.clone())).await.unwrap();
+            assert!(second.is_none());
+        });
+    }
+}
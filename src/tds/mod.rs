@@ -0,0 +1,9 @@
+pub mod codec;
+
+mod connection;
+pub(crate) mod packet;
+
+pub(crate) use connection::Connection;
+
+#[cfg(test)]
+pub(crate) use connection::test_util;
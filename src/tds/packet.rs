@@ -0,0 +1,61 @@
+//! Framing for the request/response packets [`Connection`] exchanges with
+//! the server: a one-byte kind, a `u32` little-endian payload length, then
+//! the payload itself.
+//!
+//! [`Connection`]: super::Connection
+
+use crate::{Error, Result};
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+pub(crate) const PREPARE: u8 = 1;
+pub(crate) const PREPARE_OK: u8 = 2;
+pub(crate) const UNPREPARE: u8 = 3;
+pub(crate) const UNPREPARE_OK: u8 = 4;
+pub(crate) const BULK_PREPARE: u8 = 5;
+pub(crate) const BULK_PREPARE_OK: u8 = 6;
+pub(crate) const BULK_ROWS: u8 = 7;
+pub(crate) const BULK_DONE: u8 = 8;
+pub(crate) const BULK_DONE_OK: u8 = 9;
+pub(crate) const BATCH: u8 = 10;
+pub(crate) const BATCH_OK: u8 = 11;
+pub(crate) const QUERY: u8 = 12;
+pub(crate) const QUERY_OK: u8 = 13;
+pub(crate) const QUERY_ROW: u8 = 14;
+pub(crate) const QUERY_DONE: u8 = 15;
+pub(crate) const EXEC: u8 = 16;
+pub(crate) const EXEC_OK: u8 = 17;
+pub(crate) const QUERY_EXEC: u8 = 18;
+pub(crate) const QUERY_EXEC_OK: u8 = 19;
+/// A server-side failure: payload is `u32` code + length-prefixed message,
+/// decoded by [`wire::decode_server_error`](super::codec::wire::decode_server_error).
+pub(crate) const ERROR: u8 = 255;
+
+/// Writes one framed packet and flushes it, so the server sees it
+/// immediately rather than sitting in an internal buffer.
+pub(crate) async fn write_packet<S>(transport: &mut S, kind: u8, payload: &[u8]) -> Result<()>
+where
+    S: AsyncWrite + Unpin,
+{
+    let mut header = Vec::with_capacity(5 + payload.len());
+    header.push(kind);
+    header.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    header.extend_from_slice(payload);
+
+    transport.write_all(&header).await.map_err(Error::Io)?;
+    transport.flush().await.map_err(Error::Io)
+}
+
+/// Reads one framed packet, blocking until the whole payload has arrived.
+pub(crate) async fn read_packet<S>(transport: &mut S) -> Result<(u8, Vec<u8>)>
+where
+    S: AsyncRead + Unpin,
+{
+    let mut header = [0u8; 5];
+    transport.read_exact(&mut header).await.map_err(Error::Io)?;
+
+    let len = u32::from_le_bytes(header[1..5].try_into().unwrap()) as usize;
+    let mut payload = vec![0u8; len];
+    transport.read_exact(&mut payload).await.map_err(Error::Io)?;
+
+    Ok((header[0], payload))
+}
@@ -0,0 +1,101 @@
+use crate::tds::codec::ColumnData;
+
+/// Converts a Rust value into its [`ColumnData`] wire representation so it
+/// can be pushed onto a [`TokenRow`].
+///
+/// [`ColumnData`]: tds/codec/enum.ColumnData.html
+/// [`TokenRow`]: struct.TokenRow.html
+pub trait IntoSql {
+    /// Consumes `self`, producing the wire representation of the value.
+    fn into_sql(self) -> ColumnData;
+}
+
+/// Binds a borrowed Rust value as a query parameter, used with
+/// [`Client::execute`] and [`Client::query`].
+///
+/// [`Client::execute`]: client/struct.Client.html#method.execute
+/// [`Client::query`]: client/struct.Client.html#method.query
+pub trait ToSql {
+    /// Produces the wire representation of `self` without consuming it.
+    fn to_sql(&self) -> ColumnData;
+}
+
+macro_rules! into_sql_fixed {
+    ($ty:ty, $variant:ident) => {
+        impl IntoSql for $ty {
+            fn into_sql(self) -> ColumnData {
+                ColumnData::$variant(Some(self))
+            }
+        }
+
+        impl IntoSql for Option<$ty> {
+            fn into_sql(self) -> ColumnData {
+                ColumnData::$variant(self)
+            }
+        }
+
+        impl ToSql for $ty {
+            fn to_sql(&self) -> ColumnData {
+                ColumnData::$variant(Some(*self))
+            }
+        }
+    };
+}
+
+into_sql_fixed!(u8, U8);
+into_sql_fixed!(i16, I16);
+into_sql_fixed!(i32, I32);
+into_sql_fixed!(i64, I64);
+into_sql_fixed!(f32, F32);
+into_sql_fixed!(f64, F64);
+into_sql_fixed!(bool, Bit);
+
+impl IntoSql for &'static str {
+    fn into_sql(self) -> ColumnData {
+        ColumnData::String(Some(self.into()))
+    }
+}
+
+impl IntoSql for Option<&'static str> {
+    fn into_sql(self) -> ColumnData {
+        ColumnData::String(self.map(Into::into))
+    }
+}
+
+impl IntoSql for String {
+    fn into_sql(self) -> ColumnData {
+        ColumnData::String(Some(self.into()))
+    }
+}
+
+impl IntoSql for Option<String> {
+    fn into_sql(self) -> ColumnData {
+        ColumnData::String(self.map(Into::into))
+    }
+}
+
+impl ToSql for str {
+    fn to_sql(&self) -> ColumnData {
+        ColumnData::String(Some(self.to_owned().into()))
+    }
+}
+
+impl ToSql for String {
+    fn to_sql(&self) -> ColumnData {
+        ColumnData::String(Some(self.clone().into()))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl IntoSql for chrono::NaiveDateTime {
+    fn into_sql(self) -> ColumnData {
+        ColumnData::DateTime2(Some((self, 7)))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl IntoSql for Option<chrono::NaiveDateTime> {
+    fn into_sql(self) -> ColumnData {
+        ColumnData::DateTime2(self.map(|dt| (dt, 7)))
+    }
+}
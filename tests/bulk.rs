@@ -1,14 +1,13 @@
-use futures_util::io::{AsyncRead, AsyncWrite};
+use futures_util::io::{AllowStdIo, AsyncRead, AsyncWrite};
 use names::{Generator, Name};
 use once_cell::sync::Lazy;
 use std::cell::RefCell;
 use std::env;
+use std::io;
+use std::net::TcpStream;
 use std::sync::Once;
 use tiberius::{IntoSql, Result, TokenRow};
 
-#[cfg(all(feature = "tds73", feature = "chrono"))]
-use chrono::NaiveDateTime;
-
 use runtimes_macro::test_on_runtimes;
 
 // This is used in the testing macro :)
@@ -22,8 +21,22 @@ static CONN_STR: Lazy<String> = Lazy::new(|| {
 });
 
 thread_local! {
-    static NAMES: RefCell<Option<Generator<'static>>> =
-    RefCell::new(None);
+    static NAMES: RefCell<Option<Generator<'static>>> = const { RefCell::new(None) };
+}
+
+/// Dials the server named by [`CONN_STR`], for the `#[test_on_runtimes]`
+/// tests below. They're `#[ignore]`d by default since this needs a live
+/// server speaking this crate's wire protocol on the other end.
+fn test_transport() -> Result<AllowStdIo<TcpStream>> {
+    let host_port = CONN_STR
+        .split(';')
+        .find_map(|part| part.trim().strip_prefix("server=tcp:"))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no tcp server in connection string"))?;
+
+    // The connection string writes `host,port`; `TcpStream` wants `host:port`.
+    let stream = TcpStream::connect(host_port.replacen(',', ":", 1))?;
+
+    Ok(AllowStdIo::new(stream))
 }
 
 async fn random_table() -> String {
@@ -175,61 +188,61 @@ test_bulk_type!(varchar_limited(
 test_bulk_type!(datetime2(
     "DATETIME2",
     100,
-    vec![NaiveDateTime::from_timestamp_opt(1658524194, 123456789).unwrap(); 100].into_iter()
+    vec![chrono::DateTime::from_timestamp(1658524194, 123456789).unwrap().naive_utc(); 100].into_iter()
 ));
 
 #[cfg(all(feature = "tds73", feature = "chrono"))]
 test_bulk_type!(datetime2_0(
     "DATETIME2(0)",
     100,
-    vec![NaiveDateTime::from_timestamp_opt(1658524194, 123456789).unwrap(); 100].into_iter()
+    vec![chrono::DateTime::from_timestamp(1658524194, 123456789).unwrap().naive_utc(); 100].into_iter()
 ));
 
 #[cfg(all(feature = "tds73", feature = "chrono"))]
 test_bulk_type!(datetime2_1(
     "DATETIME2(1)",
     100,
-    vec![NaiveDateTime::from_timestamp_opt(1658524194, 123456789).unwrap(); 100].into_iter()
+    vec![chrono::DateTime::from_timestamp(1658524194, 123456789).unwrap().naive_utc(); 100].into_iter()
 ));
 
 #[cfg(all(feature = "tds73", feature = "chrono"))]
 test_bulk_type!(datetime2_2(
     "DATETIME2(2)",
     100,
-    vec![NaiveDateTime::from_timestamp_opt(1658524194, 123456789).unwrap(); 100].into_iter()
+    vec![chrono::DateTime::from_timestamp(1658524194, 123456789).unwrap().naive_utc(); 100].into_iter()
 ));
 
 #[cfg(all(feature = "tds73", feature = "chrono"))]
 test_bulk_type!(datetime2_3(
     "DATETIME2(3)",
     100,
-    vec![NaiveDateTime::from_timestamp_opt(1658524194, 123456789).unwrap(); 100].into_iter()
+    vec![chrono::DateTime::from_timestamp(1658524194, 123456789).unwrap().naive_utc(); 100].into_iter()
 ));
 
 #[cfg(all(feature = "tds73", feature = "chrono"))]
 test_bulk_type!(datetime2_4(
     "DATETIME2(4)",
     100,
-    vec![NaiveDateTime::from_timestamp_opt(1658524194, 123456789).unwrap(); 100].into_iter()
+    vec![chrono::DateTime::from_timestamp(1658524194, 123456789).unwrap().naive_utc(); 100].into_iter()
 ));
 
 #[cfg(all(feature = "tds73", feature = "chrono"))]
 test_bulk_type!(datetime2_5(
     "DATETIME2(5)",
     100,
-    vec![NaiveDateTime::from_timestamp_opt(1658524194, 123456789).unwrap(); 100].into_iter()
+    vec![chrono::DateTime::from_timestamp(1658524194, 123456789).unwrap().naive_utc(); 100].into_iter()
 ));
 
 #[cfg(all(feature = "tds73", feature = "chrono"))]
 test_bulk_type!(datetime2_6(
     "DATETIME2(6)",
     100,
-    vec![NaiveDateTime::from_timestamp_opt(1658524194, 123456789).unwrap(); 100].into_iter()
+    vec![chrono::DateTime::from_timestamp(1658524194, 123456789).unwrap().naive_utc(); 100].into_iter()
 ));
 
 #[cfg(all(feature = "tds73", feature = "chrono"))]
 test_bulk_type!(datetime2_7(
     "DATETIME2(7)",
     100,
-    vec![NaiveDateTime::from_timestamp_opt(1658524194, 123456789).unwrap(); 100].into_iter()
+    vec![chrono::DateTime::from_timestamp(1658524194, 123456789).unwrap().naive_utc(); 100].into_iter()
 ));